@@ -159,6 +159,7 @@ pub struct AnimatedImage {
     loop_animation: bool,
     last_update: f32, // Store the last update time
     angle: f32, // Rotation angle
+    visible: bool, // Whether the animation should be drawn
 }
 
 impl AnimatedImage {
@@ -203,6 +204,7 @@ impl AnimatedImage {
             loop_animation,
             last_update: get_time() as f32,
             angle: 0.0,
+            visible: true,
         }
     }
     
@@ -309,6 +311,7 @@ impl AnimatedImage {
             loop_animation,
             last_update: get_time()as f32,
             angle: 0.0,
+            visible: true,
         }
     }
     
@@ -422,6 +425,7 @@ impl AnimatedImage {
                         loop_animation,
                         last_update: get_time() as f32,
                         angle: 0.0,
+                        visible: true,
                     };
                 } else {
                     // Fall back to loading as a regular texture if GIF processing fails
@@ -480,6 +484,7 @@ impl AnimatedImage {
             loop_animation,
             last_update: get_time() as f32,
             angle: 0.0,
+            visible: true,
         }
     }
 
@@ -536,6 +541,7 @@ impl AnimatedImage {
             loop_animation: false,
             last_update: 0.0,
             angle: 0.0,
+            visible: true,
         }
     }
     
@@ -598,7 +604,12 @@ impl AnimatedImage {
                 }
             }
         }
-        
+
+        // Keep the animation running but skip drawing while hidden
+        if !self.visible {
+            return;
+        }
+
         let using_frame_textures = self.frame_textures.is_some();
         let texture = if let Some(frame_textures) = &self.frame_textures {
             &frame_textures[self.current_frame]
@@ -735,6 +746,17 @@ impl AnimatedImage {
         self.width = width;
         self.height = height;
     }
+
+    // Visibility getter and setter
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(unused)]
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
     
     // Is animation finished?
     #[allow(unused)]
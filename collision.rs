@@ -26,8 +26,37 @@ let collision = check_collision(&img1, &img2, 1); //Where 1 is the number of pix
     } else {
         println!("No collision.");
     }
+
+Checking lots of objects (like 200 bullets against 50 enemies) one pair at a time is slow.
+A CollisionGrid sorts objects into squares so only objects whose boxes are close get the
+pixel check. With the other use commands add:
+
+use crate::utils::collision::{CollisionGrid, find_collisions, find_collisions_between};
+
+Then in the loop, to find every bullet that hit an enemy:
+let hits = find_collisions_between(&bullets, &enemies, 64.0, 1); //64 is the grid square size
+    for (bullet, enemy) in hits {
+        println!("Bullet {} hit enemy {}", bullet, enemy); //Indexes into the two lists
+    }
+
+Or every pair in one list that hit each other:
+let hits = find_collisions(&asteroids, 64.0, 1);
+
+To keep one grid instead, make it above the loop:
+let mut grid = CollisionGrid::new(64.0); //Squares about the size of your biggest object
+Then in the loop:
+grid.build(&enemies); //Call again whenever the enemies move
+    for bullet in &bullets {
+        for enemy in grid.collisions_with(bullet, &enemies, 1) {
+            println!("Enemy {} was hit", enemy);
+        }
+    }
+Other options:
+    grid.query(pos, size)         //Indexes of objects whose boxes overlap a rectangle
+    grid.candidate_pairs()        //Pairs whose boxes overlap, without the pixel check
 */
 
+use std::collections::{HashMap, HashSet};
 use macroquad::prelude::Vec2;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -744,3 +773,309 @@ fn calculate_rotated_bounding_box(pos: Vec2, size: Vec2, angle: f32) -> (Vec2, V
         Vec2::new(max_x - min_x + 2.0 * margin_x, max_y - min_y + 2.0 * margin_y)
     )
 }
+
+// Objects covering more squares than this go in a list checked against everything,
+// so one huge object doesn't fill thousands of squares
+const MAX_GRID_CELLS: i64 = 1024;
+
+// Check if two boxes overlap (touching edges don't count, like check_collision)
+#[inline]
+fn boxes_overlap(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> bool {
+    let overlap_w = (a.0.x + a.1.x).min(b.0.x + b.1.x) - a.0.x.max(b.0.x);
+    let overlap_h = (a.0.y + a.1.y).min(b.0.y + b.1.y) - a.0.y.max(b.0.y);
+    overlap_w > 0.0 && overlap_h > 0.0
+}
+
+// Broad phase: a uniform grid of squares, each holding the objects whose boxes touch it
+pub struct CollisionGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    boxes: Vec<(Vec2, Vec2)>, // Bounding box (pos, size) of each object, rotation included
+    large: Vec<usize>,        // Objects too big to put in squares
+}
+
+#[allow(unused)]
+impl CollisionGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            boxes: Vec::new(),
+            large: Vec::new(),
+        }
+    }
+
+    // Remove every object, cells are dropped too so a moving world doesn't keep every cell it visited
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.boxes.clear();
+        self.large.clear();
+    }
+
+    // Clear the grid and add every object, indexes match the slice
+    pub fn build<T: Collidable>(&mut self, objects: &[T]) {
+        self.clear();
+        for object in objects {
+            self.insert(object);
+        }
+    }
+
+    // Add one object and return its index
+    pub fn insert<T: Collidable>(&mut self, object: &T) -> usize {
+        let index = self.boxes.len();
+        let bounds = calculate_rotated_bounding_box(object.pos(), object.size(), object.get_angle());
+        self.boxes.push(bounds);
+
+        match self.cell_range(bounds) {
+            Some((x0, y0, x1, y1)) => {
+                for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        self.cells.entry((x, y)).or_default().push(index);
+                    }
+                }
+            }
+            None => self.large.push(index),
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.boxes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    // Indexes of the objects whose boxes overlap a rectangle, in order
+    pub fn query(&self, pos: Vec2, size: Vec2) -> Vec<usize> {
+        let area = (pos, size);
+        let mut found: Vec<usize> = self.large.clone();
+        match self.cell_range(area) {
+            Some((x0, y0, x1, y1)) => {
+                for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        if let Some(cell) = self.cells.get(&(x, y)) {
+                            found.extend(cell);
+                        }
+                    }
+                }
+            }
+            // The rectangle is huge, so just test every object
+            None => found = (0..self.boxes.len()).collect(),
+        }
+        found.sort_unstable();
+        found.dedup();
+        found.retain(|&index| boxes_overlap(self.boxes[index], area));
+        found
+    }
+
+    // Pairs (lower index first) of objects in the grid whose boxes overlap
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = HashSet::new();
+        for cell in self.cells.values() {
+            for (n, &a) in cell.iter().enumerate() {
+                for &b in &cell[n + 1..] {
+                    if boxes_overlap(self.boxes[a], self.boxes[b]) {
+                        pairs.insert((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+        for &a in &self.large {
+            for b in 0..self.boxes.len() {
+                if a != b && boxes_overlap(self.boxes[a], self.boxes[b]) {
+                    pairs.insert((a.min(b), a.max(b)));
+                }
+            }
+        }
+        let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    // Indexes of the objects (the same slice given to build) that really hit `object`
+    pub fn collisions_with<T, U>(&self, object: &T, objects: &[U], skip_pixels: usize) -> Vec<usize>
+    where
+        T: Collidable,
+        U: Collidable,
+    {
+        let (pos, size) = calculate_rotated_bounding_box(object.pos(), object.size(), object.get_angle());
+        self.query(pos, size)
+            .into_iter()
+            .filter(|&index| index < objects.len() && check_collision(object, &objects[index], skip_pixels))
+            .collect()
+    }
+
+    // The squares a box covers, None when it covers too many
+    fn cell_range(&self, (pos, size): (Vec2, Vec2)) -> Option<(i32, i32, i32, i32)> {
+        let x0 = (pos.x / self.cell_size).floor() as i32;
+        let y0 = (pos.y / self.cell_size).floor() as i32;
+        let x1 = ((pos.x + size.x.max(0.0)) / self.cell_size).floor() as i32;
+        let y1 = ((pos.y + size.y.max(0.0)) / self.cell_size).floor() as i32;
+        let cells = (x1 as i64 - x0 as i64 + 1) * (y1 as i64 - y0 as i64 + 1);
+        if cells > MAX_GRID_CELLS {
+            None
+        } else {
+            Some((x0, y0, x1, y1))
+        }
+    }
+}
+
+// Every pair of objects in one list that hit each other, as (index, index)
+pub fn find_collisions<T: Collidable>(objects: &[T], cell_size: f32, skip_pixels: usize) -> Vec<(usize, usize)> {
+    let mut grid = CollisionGrid::new(cell_size);
+    grid.build(objects);
+    grid.candidate_pairs()
+        .into_iter()
+        .filter(|&(a, b)| check_collision(&objects[a], &objects[b], skip_pixels))
+        .collect()
+}
+
+// Every object in the first list that hit one in the second, as (first index, second index)
+pub fn find_collisions_between<T, U>(first: &[T], second: &[U], cell_size: f32, skip_pixels: usize) -> Vec<(usize, usize)>
+where
+    T: Collidable,
+    U: Collidable,
+{
+    let mut grid = CollisionGrid::new(cell_size);
+    grid.build(second);
+    let mut hits = Vec::new();
+    for (a, object) in first.iter().enumerate() {
+        for b in grid.collisions_with(object, second, skip_pixels) {
+            hits.push((a, b));
+        }
+    }
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A plain rectangle, no mask so check_collision uses the boxes (or SAT when rotated)
+    struct Rect {
+        pos: Vec2,
+        size: Vec2,
+        angle: f32,
+    }
+
+    impl Collidable for Rect {
+        fn pos(&self) -> Vec2 {
+            self.pos
+        }
+        fn size(&self) -> Vec2 {
+            self.size
+        }
+        fn texture_size(&self) -> Vec2 {
+            self.size
+        }
+        fn get_mask(&self) -> Option<Vec<u8>> {
+            None
+        }
+        fn get_angle(&self) -> f32 {
+            self.angle
+        }
+    }
+
+    // The same numbers every run, so a failure can be repeated
+    fn random(seed: &mut u64, min: f32, max: f32) -> f32 {
+        *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        min + (*seed >> 40) as f32 / (1u64 << 24) as f32 * (max - min)
+    }
+
+    // Rectangles around (0, 0) so some are at negative positions, many span several squares,
+    // some sit exactly on square edges, some are rotated, and the last is too big for the grid
+    fn world(seed: u64, count: usize) -> Vec<Rect> {
+        let mut seed = seed;
+        let mut rects: Vec<Rect> = (0..count)
+            .map(|n| {
+                let mut pos = Vec2::new(random(&mut seed, -400.0, 400.0), random(&mut seed, -400.0, 400.0));
+                let mut size = Vec2::new(random(&mut seed, 1.0, 120.0), random(&mut seed, 1.0, 120.0));
+                if n % 4 == 0 {
+                    pos = (pos / 32.0).round() * 32.0;
+                    size = (size / 32.0).ceil() * 32.0;
+                }
+                let angle = if n % 5 == 0 { random(&mut seed, 0.0, 3.0) } else { 0.0 };
+                Rect { pos, size, angle }
+            })
+            .collect();
+        rects.push(Rect {
+            pos: Vec2::new(-2000.0, 50.0),
+            size: Vec2::new(4000.0, 400.0),
+            angle: 0.0,
+        });
+        rects
+    }
+
+    fn bounds(rect: &Rect) -> (Vec2, Vec2) {
+        calculate_rotated_bounding_box(rect.pos, rect.size, rect.angle)
+    }
+
+    #[test]
+    fn candidate_pairs_match_checking_every_pair() {
+        for seed in 1..6 {
+            let rects = world(seed, 150);
+            let mut grid = CollisionGrid::new(32.0);
+            grid.build(&rects);
+            assert_eq!(grid.large, [rects.len() - 1]);
+
+            let mut expected = Vec::new();
+            for a in 0..rects.len() {
+                for b in a + 1..rects.len() {
+                    if boxes_overlap(bounds(&rects[a]), bounds(&rects[b])) {
+                        expected.push((a, b));
+                    }
+                }
+            }
+            assert!(expected.iter().any(|&(_, b)| b == rects.len() - 1));
+            assert_eq!(grid.candidate_pairs(), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn query_matches_checking_every_box() {
+        let rects = world(7, 150);
+        let mut grid = CollisionGrid::new(32.0);
+        grid.build(&rects);
+
+        let mut seed = 8;
+        for _ in 0..50 {
+            let area = (
+                Vec2::new(random(&mut seed, -450.0, 450.0), random(&mut seed, -450.0, 450.0)),
+                Vec2::new(random(&mut seed, 0.5, 200.0), random(&mut seed, 0.5, 200.0)),
+            );
+            let expected: Vec<usize> = (0..rects.len()).filter(|&n| boxes_overlap(bounds(&rects[n]), area)).collect();
+            assert_eq!(grid.query(area.0, area.1), expected);
+        }
+
+        // Too many squares to look at, so every box is tested instead
+        let everything = grid.query(Vec2::new(-1.0e6, -1.0e6), Vec2::new(2.0e6, 2.0e6));
+        assert_eq!(everything, (0..rects.len()).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn find_collisions_match_check_collision() {
+        let rects = world(9, 120);
+        let mut expected = Vec::new();
+        for a in 0..rects.len() {
+            for b in a + 1..rects.len() {
+                if check_collision(&rects[a], &rects[b], 1) {
+                    expected.push((a, b));
+                }
+            }
+        }
+        assert_eq!(find_collisions(&rects, 32.0, 1), expected);
+
+        let bullets = world(10, 60);
+        let mut expected = Vec::new();
+        for (a, bullet) in bullets.iter().enumerate() {
+            for (b, rect) in rects.iter().enumerate() {
+                if check_collision(bullet, rect, 1) {
+                    expected.push((a, b));
+                }
+            }
+        }
+        assert_eq!(find_collisions_between(&bullets, &rects, 32.0, 1), expected);
+    }
+}
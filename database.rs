@@ -26,8 +26,15 @@ Turso (libSQL) database module for Rust
 
 8. Add use statement:
     use crate::utils::database::{create_database_client, DatabaseTable};
+    use crate::utils::database::Query;         // Only if you use fetch_query or delete_where
+    use crate::utils::database::Transaction;   // Only if you use run_transaction
+    use crate::utils::database::DbError;       // Only if you check which error happened
+    use crate::utils::database::PendingRequest; // Only if you run requests in the background
+    use crate::utils::database::SyncStatus;    // Only if you use the offline queue
+    use crate::utils::database::Session;       // Only if you use player accounts
 9. Add to mod.rs:
     pub mod database;
+    pub mod textfiles;   // database.rs uses TextFile to keep the offline queue and cache
 
 
 ================================
@@ -58,6 +65,37 @@ CUSTOMIZE YOUR DATABASE SCHEMA:
    - BOOLEAN → bool
    - NUMERIC → f64 or String
 
+4. Or skip steps 1 and 2 and let a struct make its own table with db_record!
+   The struct is then the only place the columns are written down:
+
+    use crate::db_record;
+    use crate::utils::database::DbRecord;
+
+    db_record! {
+        #[table("scores")]
+        pub struct Score {
+            pub player: String,
+            pub points: i64,
+            pub level: Option<i32>,   // Option columns can be empty
+        }
+    }
+
+    // Once at the start, makes the table if it doesn't exist yet
+    client.create_table_if_missing::<Score>().await?;
+
+    // Score gets an id field (0 when inserting) and works with every method
+    client.insert_record(Score::table_name(), &Score { player: "Ann".to_string(), points: 50, ..Default::default() }).await?;
+    let scores: Vec<Score> = client.fetch_table(Score::table_name()).await?;
+
+    println!("{}", Score::create_table_sql());   // The SQL, if you want to run it yourself
+
+   Columns that together can only be on one row get a UNIQUE constraint with:
+        #[table("scores", unique(player, level))]
+   Writing a second row with the same values is then refused with status 409.
+
+   Adding a field later doesn't change a table that already exists, add the column
+   yourself (ALTER TABLE scores ADD COLUMN ...) or use a new table name.
+
 ================================
 USAGE EXAMPLES:
 ================================
@@ -95,6 +133,59 @@ USAGE EXAMPLES:
     }
 
 
+// Fetch only some records with a query (filters, sorting and paging happen in the database,
+// so you don't download the whole table)
+    use crate::utils::database::Query;
+
+    // Top 10 scores for level 3, highest first
+    let top: Vec<DatabaseTable> = client
+        .fetch_query("scores", Query::new().where_eq("level", 3).order_by_desc("score").limit(10))
+        .await
+        .unwrap_or_default();
+
+    // Second page of chat messages containing "hello", oldest first, 20 per page
+    let mut query = Query::new();
+    query.where_like("text", "%hello%")
+         .where_gt("id", 0)
+         .order_by("id")
+         .limit(20)
+         .offset(20);
+    let page: Vec<DatabaseTable> = client.fetch_query("messages", &query).await.unwrap_or_default();
+
+    Filters: where_eq, where_ne, where_lt, where_le, where_gt, where_ge, where_like
+             (like uses % as "anything", for example "%hello%")
+    All filters must match. Sort by more than one column by calling order_by more than once.
+
+    // How many rows match, without downloading them (sorting and paging are ignored)
+    let hits = client.count_query("messages", Query::new().where_like("text", "%hello%")).await?;
+
+// Insert many records in one request (all are inserted, or none if one fails)
+    let new_records = vec![
+        DatabaseTable { id: 0, text: "First".to_string() },
+        DatabaseTable { id: 0, text: "Second".to_string() },
+    ];
+    if let Ok(ids) = client.insert_many("messages", &new_records).await {
+        // ids contains the new id of each record, in order
+    }
+
+// Update many records by struct in one request (all or nothing)
+    let updated_count = client.update_many("messages", &changed_records).await;
+
+// Delete every record that matches a query's filters (at least one filter is required)
+    let deleted_count = client.delete_where("messages", Query::new().where_lt("id", 100)).await;
+
+// Run several changes as one transaction (all or nothing)
+    use crate::utils::database::Transaction;
+
+    let mut transaction = Transaction::new();
+    transaction
+        .insert("messages", &new_record)
+        .update_column("players", 7, "coins", &serde_json::json!(0))
+        .delete("messages", 5);
+    if let Ok(results) = client.run_transaction(&transaction).await {
+        // One result per change, for example {"id": 12}, {"updated": 1}, {"deleted": 1}
+    }
+
 // Update a record by id (Can only do one column at a time with this method)
     if let Ok(updated_count) = client.update_record_by_id("messages", 5, "text", "New text").await {
         // updated_count is the number of records updated
@@ -102,90 +193,1178 @@ USAGE EXAMPLES:
         // Handle error
     }
 
-// Update a record by struct (update all non-id fields)
-    let updated_record = DatabaseTable { id: 5, text: "Updated text".to_string() };
-    if let Ok(updated_count) = client.update_record_by_struct("messages", &updated_record).await {
-        // updated_count is the number of records updated
-    } else {
-        // Handle error
+// Update a record by struct (update all non-id fields)
+    let updated_record = DatabaseTable { id: 5, text: "Updated text".to_string() };
+    if let Ok(updated_count) = client.update_record_by_struct("messages", &updated_record).await {
+        // updated_count is the number of records updated
+    } else {
+        // Handle error
+    }
+
+// Timeouts, retries and cancelling
+    let mut client = create_database_client();
+    client.with_timeout(5.0)        // Give up on an attempt after 5 seconds (default 10)
+          .with_retries(3)          // Try up to 3 more times after network/server errors (default 0)
+          .with_backoff(0.5, 8.0);  // Wait 0.5s before the first retry, then 1s, 2s, ... up to 8s
+
+    client.cancel_all();            // Stop every request still running (for example on a "Cancel" button)
+    A PendingRequest can also be cancelled by dropping it (loading = None).
+
+// Offline queue (for when the Wi-Fi keeps dropping)
+    let mut client = create_database_client();
+    client.with_offline_queue("db_queue.json").unwrap();   // Also loads writes queued last time
+
+    Inserts, updates and deletes that can't reach the worker are saved in the queue and
+    return Err(DbError::Queued). The queue is kept through TextFile, so it survives
+    closing the game (on the web it is kept in localStorage).
+
+    match client.insert_record("messages", &new_record).await {
+        Ok(id) => println!("Saved with id {}", id),
+        Err(DbError::Queued) => println!("Offline, will send later"),
+        Err(err) => println!("Error: {}", err),
+    }
+
+    Inside the loop, send the queue when it's time (every 5 seconds while writes are waiting):
+        if client.should_sync() {
+            syncing = Some(PendingRequest::new(client.sync()));
+        }
+        if let Some(request) = &mut syncing {
+            if request.poll().is_some() {
+                syncing = None;
+            }
+        }
+
+        match client.sync_status() {
+            SyncStatus::Synced => {}
+            SyncStatus::Waiting(count) => draw_text(&format!("{} changes waiting", count), 10.0, 20.0, 20.0, GRAY),
+            SyncStatus::Syncing(count) => draw_text(&format!("Syncing {}...", count), 10.0, 20.0, 20.0, GRAY),
+        }
+
+    Note: Queued inserts don't give you the new id, and writes made while others are
+    waiting are queued too so everything reaches the database in order.
+    A write that timed out is queued too, but it may have reached the database with only
+    the answer lost. Sending it again then inserts the record twice, so give tables a
+    unique(...) column (see db_record!) when a duplicate would matter.
+
+// Caching reads (show last-known data at once, and while offline)
+    let mut client = create_database_client();
+    client.with_cache(60.0);                             // fetch_table and fetch_record_by_id answers last 60 seconds
+    client.with_cache_file("db_cache.json").unwrap();    // Keep the cache between runs (optional)
+
+    // Right at the start, before anything has been downloaded this run
+    if let Some(records) = client.cached_table::<DatabaseTable>("messages") {
+        show(records);
+    }
+    // Then ask for fresh data. Inside the time limit the cached answer comes back without
+    // a request, and if the worker can't be reached the last answer is used instead
+    let records = client.fetch_table::<DatabaseTable>("messages").await;
+
+    Inserts, updates and deletes through this client mark that table's cached answers as
+    old, so the next read asks the worker. Changes made by other players show up once the
+    time limit runs out.
+
+    client.cache_age("messages");            // Seconds since fetch_table("messages") was cached
+    client.invalidate_cache("messages");     // Ask the worker next time
+    client.clear_cache();                    // Forget everything
+
+// Player accounts (needs the worker from db-directions.md or db_server.rs, the local
+// backends in db_local.rs don't have accounts)
+    let mut client = create_database_client();
+    client.with_api_key("my-game-key");          // Only if the server was set up with an API key
+
+    match client.register("ann", "secret123").await {    // Or client.login(...) next time
+        Ok(session) => println!("Playing as {} (id {})", session.username, session.player_id),
+        Err(err) => error_box = MessageBox::info("Login", err.user_message()),   // "Username taken", ...
+    }
+
+    // Stay logged in between runs
+    if let Some(session) = client.session() {
+        TextFile::save_json("session.json", &session).await.ok();
+    }
+    if let Ok(session) = TextFile::load_json::<Session>("session.json").await {
+        client.set_session(Some(session));
+    }
+
+    client.logout().await.ok();
+
+    Any table with an owner_id column belongs to its players: inserting needs a login and
+    the server fills in owner_id, and players can only update or delete their own rows
+    (changes to other players' rows change nothing). Everyone can still read every row.
+    With db_record! add the column as a field:
+        pub owner_id: Option<i64>,
+
+// No network (a local database for development and tests)
+// db_local.rs has backends that answer the same requests as the worker, so all of the
+// methods above work unchanged:
+    let client = create_memory_client();                            // Tables in memory
+    let client = create_sqlite_client("dev.db", SCHEMA).unwrap();   // An SQLite file on desktop
+    let client = DatabaseClient::from_backend(my_backend);          // Anything implementing DatabaseBackend
+
+// Other cloud databases
+// db_providers.rs has backends for Supabase, Firebase and Postgres REST servers (like Neon):
+    let client = create_supabase_client("https://abcd.supabase.co", "your-anon-key");
+    let client = create_firebase_client("my-project-id", "your-web-api-key");
+
+// Requests without freezing the game
+// Awaiting a request pauses your loop until the answer comes back. To keep drawing
+// (spinners, ProgressBars, ...) while it runs, start it as a PendingRequest instead:
+    use crate::utils::database::PendingRequest;
+
+    Above the loop:
+        let mut loading: Option<PendingRequest<Vec<DatabaseTable>>> = None;
+
+    When you want the data (for example when a button is clicked):
+        loading = Some(PendingRequest::new(client.fetch_table("messages")));
+
+    Inside the loop, every frame:
+        if let Some(request) = &mut loading {
+            if let Some(result) = request.poll() {
+                match result {
+                    Ok(fetched) => records = fetched,
+                    Err(err) => println!("Error fetching records: {}", err),
+                }
+                loading = None;
+            } else {
+                // Still waiting, draw a spinner or "Loading..."
+            }
+        }
+
+    Any DatabaseClient method works. If it takes a Query, keep the Query in a variable
+    (not inline) so it lives as long as the request.
+
+// Handling errors
+// Every method returns a DbError when something goes wrong:
+//   Network, Timeout, Http { status, body }, Decode, NotFound, Worker { message }, Invalid,
+//   Cancelled, Queued (offline queue only) and Storage (offline queue and cache file only)
+    use crate::utils::database::DbError;
+
+    match client.fetch_table::<DatabaseTable>("messages").await {
+        Ok(records) => { /* use the records */ }
+        Err(err) => {
+            println!("{}", err);                                   // Details for you
+            error_box = MessageBox::info("Error", err.user_message()); // Friendly text for players
+            if err.is_retryable() {
+                // Network trouble or a server error, worth trying again later
+            }
+        }
+    }
+
+// Delete a record by id (from user id input)
+    if let Ok(deleted_count) = client.delete_record_by_id("messages", 5).await {
+        // deleted_count is the number of records deleted
+    } else {
+        // Handle error
+    }
+
+
+// Displaying records in a ListView:
+//Where 'list_view' is your ListView instance and 'records' is the Vec<DatabaseTable> fetched from the database.
+//Change the items.push! line to customize how each record is displayed in the list.
+   
+   fn update_listview(list_view: &mut ListView, messages: &Vec<DatabaseTable>) {
+    list_view.clear();
+    let mut items: Vec<String> = Vec::new();
+    for (i, msg) in messages.iter().enumerate() {
+        items.push(format!("  {}: ID={}, Text={}", i + 1, msg.id, msg.text));
+    }
+    list_view.add_items(&items);
+}
+*/
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use ureq;
+use macroquad::prelude::next_frame;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use crate::utils::textfiles::TextFile;
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Everything that can go wrong with a database request
+/// Use is_retryable() to decide whether to try again, and user_message() for a MessageBox
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbError {
+    Network(String),                    // Couldn't reach the worker (no internet, wrong URL, ...)
+    Timeout,                            // The worker didn't answer in time
+    Http { status: u16, body: String }, // The worker answered with an error status
+    Decode(String),                     // The answer wasn't what was expected
+    NotFound,                           // The worker (or what was asked for) doesn't exist
+    Worker { message: String },         // The worker reported an error in its answer
+    Invalid(String),                    // The request was wrong, so it was never sent
+    Cancelled,                          // DatabaseClient::cancel_all() was called
+    Queued,                             // Offline, the write was saved and will be sent by sync()
+    Storage(String),                    // The offline queue or cache file couldn't be saved or loaded
+}
+
+#[allow(unused)]
+impl DbError {
+    fn from_status(status: u16, body: String) -> Self {
+        if status == 404 {
+            DbError::NotFound
+        } else {
+            DbError::Http { status, body }
+        }
+    }
+
+    /// True for errors that might go away if the same request is sent again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DbError::Network(_) | DbError::Timeout => true,
+            DbError::Http { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// A short message that can be shown to players
+    pub fn user_message(&self) -> String {
+        match self {
+            DbError::Network(_) => "Can't connect to the server. Check your internet connection.".to_string(),
+            DbError::Timeout => "The server took too long to answer. Please try again.".to_string(),
+            DbError::Http { status, .. } if *status >= 500 => "The server had a problem. Please try again later.".to_string(),
+            // Login problems ("Wrong username or password", "Login required", ...) are written for players
+            DbError::Http { status: 401 | 403 | 409, body } if !body.is_empty() => body.clone(),
+            DbError::Http { status, .. } => format!("The server refused the request (error {}).", status),
+            DbError::Decode(_) => "The server sent an answer the game doesn't understand.".to_string(),
+            DbError::NotFound => "The server couldn't be found.".to_string(),
+            DbError::Worker { message } => format!("The server reported an error: {}", message),
+            DbError::Invalid(message) => format!("Invalid request: {}", message),
+            DbError::Cancelled => "The request was cancelled.".to_string(),
+            DbError::Queued => "You're offline. Your changes will be sent when you reconnect.".to_string(),
+            DbError::Storage(_) => "Your changes couldn't be saved on this device.".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbError::Network(message) => write!(f, "Network error: {}", message),
+            DbError::Timeout => write!(f, "Request timed out"),
+            DbError::Http { status, body } => write!(f, "HTTP {} error: {}", status, body),
+            DbError::Decode(message) => write!(f, "Could not read response: {}", message),
+            DbError::NotFound => write!(f, "Not found"),
+            DbError::Worker { message } => write!(f, "Worker error: {}", message),
+            DbError::Invalid(message) => write!(f, "Invalid request: {}", message),
+            DbError::Cancelled => write!(f, "Request cancelled"),
+            DbError::Queued => write!(f, "Offline, write queued for sync"),
+            DbError::Storage(message) => write!(f, "Storage error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl From<serde_json::Error> for DbError {
+    fn from(error: serde_json::Error) -> Self {
+        DbError::Decode(error.to_string())
+    }
+}
+
+// The records from a {"records": [...]} answer
+fn records_from<T: for<'de> Deserialize<'de>>(response: &serde_json::Value) -> Result<Vec<T>, DbError> {
+    let records = response["records"].as_array().cloned().unwrap_or_default();
+    let mut result = Vec::new();
+    for record in records {
+        result.push(serde_json::from_value(record)?);
+    }
+    Ok(result)
+}
+
+// The record from a {"record": ...} answer, None when it is null
+fn record_from<T: for<'de> Deserialize<'de>>(response: &serde_json::Value) -> Result<Option<T>, DbError> {
+    match response.get("record") {
+        Some(val) if !val.is_null() => Ok(Some(serde_json::from_value(val.clone())?)),
+        _ => Ok(None),
+    }
+}
+
+// Turn the worker's reply into JSON, checking for {"error": ...} answers
+fn parse_response(text: &str) -> Result<serde_json::Value, DbError> {
+    let json: serde_json::Value = serde_json::from_str(text)?;
+    if let Some(message) = json.get("error").filter(|error| !error.is_null()) {
+        let message = message.as_str().map(|m| m.to_string()).unwrap_or_else(|| message.to_string());
+        return Err(DbError::Worker { message });
+    }
+    Ok(json)
+}
+
+// ============================================================================
+// REQUESTS THAT DON'T FREEZE THE GAME
+// ============================================================================
+
+// The request a PendingRequest (or a DatabaseBackend) is working on
+pub type RequestFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, DbError>> + 'a>>;
+
+/// A database request running in the background
+/// Call poll() once per frame, it gives the result back when the request is finished
+pub struct PendingRequest<'a, T> {
+    future: Option<RequestFuture<'a, T>>,
+}
+
+#[allow(unused)]
+impl<'a, T> PendingRequest<'a, T> {
+    /// Start any DatabaseClient request, for example
+    /// PendingRequest::new(client.fetch_table::<DatabaseTable>("messages"))
+    pub fn new<F: Future<Output = Result<T, DbError>> + 'a>(request: F) -> Self {
+        Self {
+            future: Some(Box::pin(request)),
+        }
+    }
+
+    /// Check on the request, returns Some(result) once when it finishes and None otherwise
+    pub fn poll(&mut self) -> Option<Result<T, DbError>> {
+        let future = self.future.as_mut()?;
+        let mut context = Context::from_waker(Waker::noop());
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(result) => {
+                self.future = None;
+                Some(result)
+            }
+            Poll::Pending => None,
+        }
+    }
+
+    /// True until poll() has returned the result
+    pub fn is_pending(&self) -> bool {
+        self.future.is_some()
+    }
+}
+
+/// How long a request may take, how to tell it was cancelled and who is sending it
+pub struct RequestContext<'a> {
+    deadline: f64,                   // Time (from now()) when the request gives up
+    timeout: f64,                    // Seconds, also used as the socket timeout on desktop
+    cancelled: &'a dyn Fn() -> bool,
+    api_key: Option<&'a str>,        // Sent as the X-Api-Key header
+    token: Option<&'a str>,          // Sent as "Authorization: Bearer <token>"
+}
+
+#[allow(unused)]
+impl RequestContext<'_> {
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key
+    }
+
+    /// The session token of the logged in player (or the token from with_token)
+    pub fn token(&self) -> Option<&str> {
+        self.token
+    }
+
+    // Headers for a request to the worker
+    fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(key) = self.api_key {
+            headers.push(("X-Api-Key".to_string(), key.to_string()));
+        }
+        if let Some(token) = self.token {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+        headers
+    }
+
+    /// Err(Cancelled) or Err(Timeout) once the request should stop, check it every frame while waiting
+    pub fn check(&self) -> Result<(), DbError> {
+        if (self.cancelled)() {
+            Err(DbError::Cancelled)
+        } else if now() >= self.deadline {
+            Err(DbError::Timeout)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Seconds since Jan 1 1970, works on desktop and web
+fn now() -> f64 {
+    macroquad::miniquad::date::now()
+}
+
+// Send the JSON body to the worker and return the text of its answer
+async fn post_json(url: &str, body: String, context: &RequestContext<'_>) -> Result<String, DbError> {
+    http_request("POST", url, &context.headers(), Some(body), context).await
+}
+
+/// Send any HTTP request and return the text of the answer, for backends that talk to
+/// other services (see db_providers.rs). Error statuses become DbError::Http (NotFound for 404)
+/// On desktop the request runs on its own thread and we wait a frame at a time,
+/// so the game keeps drawing while it waits
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn http_request(
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<String>,
+    context: &RequestContext<'_>,
+) -> Result<String, DbError> {
+    use std::sync::mpsc::{channel, TryRecvError};
+
+    let (sender, receiver) = channel();
+    let method = method.to_string();
+    let url = url.to_string();
+    let headers = headers.to_vec();
+    let timeout = std::time::Duration::from_secs_f64(context.timeout.max(0.001));
+    std::thread::spawn(move || {
+        // The receiver is gone if the request was dropped, nothing to do then
+        let _ = sender.send(http_request_blocking(&method, &url, &headers, body.as_deref(), timeout));
+    });
+
+    loop {
+        match receiver.try_recv() {
+            Ok(result) => return result,
+            Err(TryRecvError::Empty) => {
+                context.check()?;
+                next_frame().await
+            }
+            Err(TryRecvError::Disconnected) => {
+                return Err(DbError::Network("request thread stopped".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn http_request_blocking(
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<&str>,
+    timeout: std::time::Duration,
+) -> Result<String, DbError> {
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+    let mut request = agent.request(method, url);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    let response = match body {
+        Some(body) => request.send_string(body),
+        None => request.call(),
+    };
+    match response {
+        Ok(resp) => resp.into_string().map_err(|e| DbError::Network(e.to_string())),
+        Err(ureq::Error::Status(code, resp)) => {
+            let err_body = resp.into_string().unwrap_or_else(|_| "Could not read error body".to_string());
+            Err(DbError::from_status(code, err_body))
+        }
+        Err(e) => Err(DbError::Network(e.to_string())),
+    }
+}
+
+// On the web the browser's fetch does the request (see dusome_bundle.js),
+// each request gets an id so several can run at once
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "env")]
+unsafe extern "C" {
+    fn mq_db_request_start(
+        method_ptr: *const u8,
+        method_len: usize,
+        ptr: *const u8,
+        len: usize,
+        url_ptr: *const u8,
+        url_len: usize,
+        headers_ptr: *const u8,
+        headers_len: usize,
+    ) -> u32;
+    fn mq_db_request_len(id: u32) -> i32;
+    fn mq_db_request_take(id: u32, ptr: *mut u8);
+    fn mq_db_request_cancel(id: u32);
+}
+
+// Forgets the request in JS if it is dropped before it finished
+#[cfg(target_arch = "wasm32")]
+struct JsRequest(u32);
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for JsRequest {
+    fn drop(&mut self) {
+        unsafe { mq_db_request_cancel(self.0) };
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn http_request(
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<String>,
+    context: &RequestContext<'_>,
+) -> Result<String, DbError> {
+    let headers: serde_json::Map<String, serde_json::Value> =
+        headers.iter().map(|(name, value)| (name.clone(), value.clone().into())).collect();
+    let headers = serde_json::Value::Object(headers).to_string();
+    // An empty body is sent as no body at all
+    let body = body.unwrap_or_default();
+    let request = JsRequest(unsafe {
+        mq_db_request_start(
+            method.as_ptr(),
+            method.len(),
+            body.as_ptr(),
+            body.len(),
+            url.as_ptr(),
+            url.len(),
+            headers.as_ptr(),
+            headers.len(),
+        )
+    });
+
+    let result_len = loop {
+        let len = unsafe { mq_db_request_len(request.0) };
+        if len >= 0 {
+            break len as usize;
+        }
+        context.check()?;
+        next_frame().await;
+    };
+
+    let mut buf = vec![0u8; result_len];
+    unsafe { mq_db_request_take(request.0, buf.as_mut_ptr()) };
+    let text = String::from_utf8(buf).map_err(|e| DbError::Decode(format!("UTF-8 error: {}", e)))?;
+
+    // JS wraps the reply as {"status", "body"}, or {"error", "message"} if fetch failed
+    let reply: serde_json::Value = serde_json::from_str(&text)?;
+    if reply["error"].is_string() {
+        return Err(DbError::Network(reply["message"].as_str().unwrap_or("fetch failed").to_string()));
+    }
+    let status = reply["status"].as_u64().unwrap_or(200) as u16;
+    let body = reply["body"].as_str().unwrap_or_default().to_string();
+    if !(200..300).contains(&status) {
+        return Err(DbError::from_status(status, body));
+    }
+    Ok(body)
+}
+
+// ============================================================================
+// BACKENDS
+// ============================================================================
+
+/// Where a DatabaseClient sends its requests
+/// Every backend takes the same JSON requests as the worker in db-directions.md
+/// (for example {"action": "fetch", "table": "messages"}) and answers with the same JSON,
+/// so every DatabaseClient method works with any backend
+pub trait DatabaseBackend {
+    /// Run one request. Backends that wait should call context.check() while they wait
+    fn execute<'a>(&'a self, payload: &'a serde_json::Value, context: &'a RequestContext<'a>) -> RequestFuture<'a, serde_json::Value>;
+}
+
+// Request checks shared by the backends in db_local.rs and db_providers.rs, so they all
+// refuse the same requests the worker does, with the same errors
+
+// Actions that change data (these can be used inside a transaction)
+pub(crate) const WRITE_ACTIONS: [&str; 7] = ["insert", "update", "update_by_column", "delete", "insert_many", "update_many", "delete_where"];
+
+// The worker answers a bad request with status 400
+pub(crate) fn bad_request(message: &str) -> DbError {
+    DbError::Http {
+        status: 400,
+        body: message.to_string(),
+    }
+}
+
+// Only allow safe SQL identifiers (table and column names)
+pub(crate) fn safe_identifier(name: &str) -> Result<&str, DbError> {
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(name)
+    } else {
+        Err(bad_request("Invalid identifier"))
+    }
+}
+
+// A table or column name from the request
+pub(crate) fn identifier(name: &serde_json::Value) -> Result<&str, DbError> {
+    safe_identifier(name.as_str().unwrap_or_default())
+}
+
+// Checks the action and table the same way the worker does
+pub(crate) fn request_action(data: &serde_json::Value) -> Result<&str, DbError> {
+    let action = data["action"].as_str().unwrap_or_default();
+    if action.is_empty() || (!data["table"].is_string() && action != "transaction") {
+        return Err(bad_request("Missing action or table"));
+    }
+    if !matches!(action, "fetch" | "fetch_by_id" | "query" | "count" | "transaction" | "create_table") && !WRITE_ACTIONS.contains(&action) {
+        return Err(bad_request("Unknown action"));
+    }
+    Ok(action)
+}
+
+// The operations of a transaction, all of them must be writes
+pub(crate) fn transaction_operations(data: &serde_json::Value) -> Result<&Vec<serde_json::Value>, DbError> {
+    let operations = match data["operations"].as_array() {
+        Some(operations) if !operations.is_empty() => operations,
+        _ => return Err(bad_request("Missing operations")),
+    };
+    for op in operations {
+        let action = op["action"].as_str().unwrap_or_default();
+        if !WRITE_ACTIONS.contains(&action) {
+            return Err(bad_request(&format!("Action not allowed in a transaction: {}", action)));
+        }
+    }
+    Ok(operations)
+}
+
+// An id the worker accepts (it treats a missing id or 0 as missing)
+pub(crate) fn required_id(op: &serde_json::Value, message: &str) -> Result<i64, DbError> {
+    op["id"].as_i64().filter(|id| *id != 0).ok_or_else(|| bad_request(message))
+}
+
+// The columns of a record to insert or update, without the id
+pub(crate) fn record_fields(record: &serde_json::Value) -> Result<Vec<(&str, &serde_json::Value)>, DbError> {
+    let record = record.as_object().ok_or_else(|| bad_request("Missing record"))?;
+    let mut fields = Vec::new();
+    for (key, value) in record {
+        if key != "id" {
+            fields.push((safe_identifier(key)?, value));
+        }
+    }
+    Ok(fields)
+}
+
+// The columns an update sets, an update without any is refused
+pub(crate) fn update_fields(record: &serde_json::Value) -> Result<Vec<(&str, &serde_json::Value)>, DbError> {
+    let fields = record_fields(record)?;
+    if fields.is_empty() {
+        return Err(bad_request("No columns to update"));
+    }
+    Ok(fields)
+}
+
+// The id and column of an update_by_column, rows are found by id so it can't be changed
+pub(crate) fn column_update(op: &serde_json::Value) -> Result<(i64, &str), DbError> {
+    let id = required_id(op, "Missing id or column")?;
+    let column = identifier(&op["column"]).map_err(|_| bad_request("Missing id or column"))?;
+    if column == "id" {
+        return Err(bad_request("id can't be changed"));
+    }
+    Ok((id, column))
+}
+
+/// The Cloudflare worker (or any server speaking its protocol) at a URL
+pub struct WorkerBackend {
+    url: String,
+}
+
+impl WorkerBackend {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.to_string() }
+    }
+}
+
+impl DatabaseBackend for WorkerBackend {
+    fn execute<'a>(&'a self, payload: &'a serde_json::Value, context: &'a RequestContext<'a>) -> RequestFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let text = post_json(&self.url, payload.to_string(), context).await?;
+            parse_response(&text)
+        })
+    }
+}
+
+// ============================================================================
+// PLAYER ACCOUNTS
+// ============================================================================
+
+/// A logged in player, from DatabaseClient::login or register
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub player_id: i64,
+    pub username: String,
+    pub token: String, // Sent as "Authorization: Bearer <token>", keep it secret
+}
+
+// ============================================================================
+// OFFLINE QUEUE
+// ============================================================================
+
+/// Where the offline queue is at, for showing "syncing..." in the game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncStatus {
+    Synced,         // Nothing waiting to be sent
+    Waiting(usize), // This many writes are waiting for the connection to come back
+    Syncing(usize), // sync() is sending the waiting writes right now
+}
+
+// A write that couldn't be sent, saved with the time it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedWrite {
+    payload: serde_json::Value,
+    queued_at: f64,
+}
+
+struct OfflineQueue {
+    path: String,
+    writes: Vec<QueuedWrite>,
+    syncing: bool,
+    sync_interval: f64, // Seconds between automatic sync attempts (see should_sync)
+    last_attempt: f64,
+    last_error: Option<DbError>,
+}
+
+impl OfflineQueue {
+    // Load a queue saved by an earlier run (empty if there isn't one)
+    fn load(path: &str) -> Result<Self, DbError> {
+        let saved = TextFile::backend().read(path).map_err(DbError::Storage)?;
+        let writes = match saved {
+            Some(json) => serde_json::from_str(&json).map_err(|e| DbError::Storage(e.to_string()))?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            path: path.to_string(),
+            writes,
+            syncing: false,
+            sync_interval: 5.0,
+            last_attempt: 0.0,
+            last_error: None,
+        })
+    }
+
+    fn save(&self) -> Result<(), DbError> {
+        let json = serde_json::to_string(&self.writes).map_err(|e| DbError::Storage(e.to_string()))?;
+        TextFile::backend().write(&self.path, &json).map_err(DbError::Storage)
+    }
+}
+
+// Clears the syncing flag even if a sync is cancelled or dropped part way
+struct SyncGuard<'a>(&'a RefCell<OfflineQueue>);
+
+impl Drop for SyncGuard<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().syncing = false;
+    }
+}
+
+// ============================================================================
+// RESPONSE CACHE
+// ============================================================================
+
+// One cached answer, id is None for fetch_table and Some for fetch_record_by_id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    table: String,
+    id: Option<i64>,
+    response: serde_json::Value,
+    stored_at: f64, // Wall clock seconds, 0 once a write has made it old
+}
+
+struct ResponseCache {
+    path: Option<String>,
+    ttl: f64,
+    entries: Vec<CacheEntry>,
+    writes: u64, // Goes up with every invalidate, so answers that raced a write can be spotted
+}
+
+impl ResponseCache {
+    // The cached answer, however old it is
+    fn get(&self, table: &str, id: Option<i64>) -> Option<&CacheEntry> {
+        self.entries.iter().find(|entry| entry.table == table && entry.id == id)
+    }
+
+    // The cached answer if it is still inside the time limit
+    fn fresh(&self, table: &str, id: Option<i64>) -> Option<serde_json::Value> {
+        self.get(table, id)
+            .filter(|entry| now() - entry.stored_at < self.ttl)
+            .map(|entry| entry.response.clone())
+    }
+
+    // `writes` is what self.writes was when the request was sent. If a write happened
+    // since, the answer may be from before it, so it is kept as already old
+    fn store(&mut self, table: &str, id: Option<i64>, response: &serde_json::Value, writes: u64) {
+        self.entries.retain(|entry| entry.table != table || entry.id != id);
+        self.entries.push(CacheEntry {
+            table: table.to_string(),
+            id,
+            response: response.clone(),
+            stored_at: if writes == self.writes { now() } else { 0.0 },
+        });
+        // A cache that can't be saved still works for this run
+        let _ = self.save();
+    }
+
+    // Old answers are kept (not removed) so they can still be used while offline
+    fn invalidate(&mut self, table: &str) {
+        self.writes += 1;
+        for entry in self.entries.iter_mut().filter(|entry| entry.table == table) {
+            entry.stored_at = 0.0;
+        }
+        let _ = self.save();
+    }
+
+    fn load(&mut self, path: &str) -> Result<(), DbError> {
+        if let Some(json) = TextFile::backend().read(path).map_err(DbError::Storage)? {
+            self.entries = serde_json::from_str(&json).map_err(|e| DbError::Storage(e.to_string()))?;
+        }
+        self.path = Some(path.to_string());
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), DbError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string(&self.entries).map_err(|e| DbError::Storage(e.to_string()))?;
+        TextFile::backend().write(path, &json).map_err(DbError::Storage)
+    }
+}
+
+// Helper function for serde to skip serializing id when it's 0 (pub so db_record! can use it)
+pub fn is_zero(num: &i32) -> bool {
+    *num == 0
+}
+
+// URL of your Cloudflare Worker backend
+pub const WORKER_URL: &str = "https://db-worker.mathew-dusome.workers.dev";
+
+
+// ============================================================================
+// CUSTOMIZE THIS STRUCT FOR YOUR DATABASE SCHEMA
+// ============================================================================
+
+/// Your database record struct - used for both INSERT and SELECT operations
+/// When inserting: set id to 0 (it will be auto-generated by the database)
+/// When fetching: id will be populated with the actual ID
+/// 
+/// Modify this struct to match your table columns:
+/// #[derive(Debug, Deserialize, Serialize, Clone)]
+/// pub struct DatabaseTable {
+///     #[serde(default, skip_serializing_if = "is_zero")]
+///     pub id: i32,
+///     pub email: String,
+///     pub age: i32,
+///     pub active: bool,
+///     pub score: f64,
+/// }
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DatabaseTable {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub id: i32,
+    pub text: String,
+    // Example: Add more fields like this:
+    // pub email: String,
+    // pub age: i32,
+    // pub active: bool,
+    // pub score: f64,
+}
+
+// ============================================================================
+// TABLES FROM STRUCTS
+// ============================================================================
+
+/// One column of a table, sent to the worker as {"name", "type", "nullable"}
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbColumn {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub sql_type: &'static str,
+    pub nullable: bool,
+}
+
+impl DbColumn {
+    /// The column for a field of type T
+    pub fn of<T: DbType>(name: &'static str) -> Self {
+        Self {
+            name,
+            sql_type: T::SQL_TYPE,
+            nullable: T::NULLABLE,
+        }
+    }
+}
+
+/// Rust types that can be table columns, and the SQL type they are stored as
+pub trait DbType {
+    const SQL_TYPE: &'static str;
+    const NULLABLE: bool = false;
+}
+
+impl DbType for i32 {
+    const SQL_TYPE: &'static str = "INTEGER";
+}
+impl DbType for i64 {
+    const SQL_TYPE: &'static str = "INTEGER";
+}
+impl DbType for u32 {
+    const SQL_TYPE: &'static str = "INTEGER";
+}
+impl DbType for f32 {
+    const SQL_TYPE: &'static str = "REAL";
+}
+impl DbType for f64 {
+    const SQL_TYPE: &'static str = "REAL";
+}
+impl DbType for bool {
+    const SQL_TYPE: &'static str = "BOOLEAN";
+}
+impl DbType for String {
+    const SQL_TYPE: &'static str = "TEXT";
+}
+// Option columns can be empty (NULL)
+impl<T: DbType> DbType for Option<T> {
+    const SQL_TYPE: &'static str = T::SQL_TYPE;
+    const NULLABLE: bool = true;
+}
+
+/// A struct that is stored as a table, usually made with db_record!
+/// The table always has an id column first, columns() lists the others
+pub trait DbRecord: Serialize + DeserializeOwned {
+    fn table_name() -> &'static str;
+    fn columns() -> Vec<DbColumn>;
+
+    /// Columns that together can only be on one row, empty for none
+    fn unique_columns() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// The CREATE TABLE statement for the table (the same one create_table_if_missing runs)
+    fn create_table_sql() -> String {
+        let mut sql = format!("CREATE TABLE IF NOT EXISTS {} (id INTEGER PRIMARY KEY AUTOINCREMENT", Self::table_name());
+        for column in Self::columns() {
+            sql += &format!(", {} {}{}", column.name, column.sql_type, if column.nullable { "" } else { " NOT NULL" });
+        }
+        let unique = Self::unique_columns();
+        if !unique.is_empty() {
+            sql += &format!(", UNIQUE({})", unique.join(", "));
+        }
+        sql + ")"
+    }
+}
+
+/// Make a record struct and its table from one definition. The struct gets an id field
+/// (0 when inserting) and Debug, Clone, Default, Serialize and Deserialize.
+///     db_record! {
+///         #[table("scores")]
+///         pub struct Score {
+///             pub player: String,
+///             pub points: i64,
+///             pub level: Option<i32>,
+///         }
+///     }
+/// Columns are named after the fields, so don't rename fields with serde.
+/// `#[table("scores", unique(player, level))]` also makes those columns unique together.
+#[macro_export]
+macro_rules! db_record {
+    (
+        #[table($table:literal $(, unique($($unique:ident),+ $(,)?))?)]
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident : $field_type:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+        $vis struct $name {
+            #[serde(default, skip_serializing_if = "crate::utils::database::is_zero")]
+            pub id: i32,
+            $($(#[$field_meta])* $field_vis $field: $field_type,)*
+        }
+
+        impl $crate::utils::database::DbRecord for $name {
+            fn table_name() -> &'static str {
+                $table
+            }
+
+            fn columns() -> Vec<$crate::utils::database::DbColumn> {
+                vec![$($crate::utils::database::DbColumn::of::<$field_type>(stringify!($field))),*]
+            }
+
+            fn unique_columns() -> Vec<&'static str> {
+                vec![$($(stringify!($unique)),+)?]
+            }
+        }
+    };
+}
+
+// ============================================================================
+// CONVENIENCE FUNCTIONS
+// ============================================================================
+
+#[allow(unused)]
+pub fn create_database_client() -> DatabaseClient {
+    DatabaseClient::new(WORKER_URL.to_string())
+}
+
+
+// ============================================================================
+// QUERY BUILDER
+// ============================================================================
+
+/// One filter in a query, sent to the worker as {"column", "op", "value"}
+#[derive(Debug, Clone, Serialize)]
+pub struct Filter {
+    pub column: String,
+    pub op: String,
+    pub value: serde_json::Value,
+}
+
+/// One sort column in a query
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderBy {
+    pub column: String,
+    pub descending: bool,
+}
+
+/// Filters, sorting and paging for fetch_query
+/// Sent to the worker as {"action": "query", "where": [...], "order_by": [...], "limit", "offset"}
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Query {
+    #[serde(rename = "where", skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<Filter>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_by: Vec<OrderBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+#[allow(unused)]
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn filter(&mut self, column: &str, op: &str, value: serde_json::Value) -> &mut Self {
+        self.filters.push(Filter {
+            column: column.to_string(),
+            op: op.to_string(),
+            value,
+        });
+        self
+    }
+
+    /// column = value
+    pub fn where_eq<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, "=", value.into())
+    }
+
+    /// column != value
+    pub fn where_ne<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, "!=", value.into())
+    }
+
+    /// column < value
+    pub fn where_lt<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, "<", value.into())
+    }
+
+    /// column <= value
+    pub fn where_le<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, "<=", value.into())
+    }
+
+    /// column > value
+    pub fn where_gt<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, ">", value.into())
+    }
+
+    /// column >= value
+    pub fn where_ge<V: Into<serde_json::Value>>(&mut self, column: &str, value: V) -> &mut Self {
+        self.filter(column, ">=", value.into())
+    }
+
+    /// column LIKE pattern (% matches anything, _ matches one character)
+    pub fn where_like(&mut self, column: &str, pattern: &str) -> &mut Self {
+        self.filter(column, "like", pattern.into())
+    }
+
+    /// Sort smallest first (call again to sort by more columns)
+    pub fn order_by(&mut self, column: &str) -> &mut Self {
+        self.order_by.push(OrderBy {
+            column: column.to_string(),
+            descending: false,
+        });
+        self
+    }
+
+    /// Sort largest first
+    pub fn order_by_desc(&mut self, column: &str) -> &mut Self {
+        self.order_by.push(OrderBy {
+            column: column.to_string(),
+            descending: true,
+        });
+        self
     }
 
-// Delete a record by id (from user id input)
-    if let Ok(deleted_count) = client.delete_record_by_id("messages", 5).await {
-        // deleted_count is the number of records deleted
-    } else {
-        // Handle error
+    /// Return at most this many records
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
     }
 
-
-// Displaying records in a ListView:
-//Where 'list_view' is your ListView instance and 'records' is the Vec<DatabaseTable> fetched from the database.
-//Change the items.push! line to customize how each record is displayed in the list.
-   
-   fn update_listview(list_view: &mut ListView, messages: &Vec<DatabaseTable>) {
-    list_view.clear();
-    let mut items: Vec<String> = Vec::new();
-    for (i, msg) in messages.iter().enumerate() {
-        items.push(format!("  {}: ID={}, Text={}", i + 1, msg.id, msg.text));
+    /// Skip this many records first (for paging, use with limit)
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = Some(offset);
+        self
     }
-    list_view.add_items(&items);
 }
-*/
 
-use serde::{Deserialize, Serialize};
-#[cfg(not(target_arch = "wasm32"))]
-use ureq;
-#[cfg(target_arch = "wasm32")]
-use macroquad::prelude::next_frame;
+// ============================================================================
+// TRANSACTIONS
+// ============================================================================
 
-// Helper function for serde to skip serializing id when it's 0
-fn is_zero(num: &i32) -> bool {
-    *num == 0
+/// A list of changes sent in one request and applied all-or-nothing
+/// Build it up, then pass it to DatabaseClient::run_transaction
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    operations: Vec<serde_json::Value>,
 }
 
-// URL of your Cloudflare Worker backend
-pub const WORKER_URL: &str = "https://db-worker.mathew-dusome.workers.dev";
+#[allow(unused)]
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
+    pub fn insert<T: Serialize>(&mut self, table: &str, record: &T) -> &mut Self {
+        self.push(serde_json::json!({ "action": "insert", "table": table, "record": record }))
+    }
 
-// ============================================================================
-// CUSTOMIZE THIS STRUCT FOR YOUR DATABASE SCHEMA
-// ============================================================================
+    pub fn insert_many<T: Serialize>(&mut self, table: &str, records: &[T]) -> &mut Self {
+        self.push(serde_json::json!({ "action": "insert_many", "table": table, "records": records }))
+    }
 
-/// Your database record struct - used for both INSERT and SELECT operations
-/// When inserting: set id to 0 (it will be auto-generated by the database)
-/// When fetching: id will be populated with the actual ID
-/// 
-/// Modify this struct to match your table columns:
-/// #[derive(Debug, Deserialize, Serialize, Clone)]
-/// pub struct DatabaseTable {
-///     #[serde(default, skip_serializing_if = "is_zero")]
-///     pub id: i32,
-///     pub email: String,
-///     pub age: i32,
-///     pub active: bool,
-///     pub score: f64,
-/// }
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct DatabaseTable {
-    #[serde(default, skip_serializing_if = "is_zero")]
-    pub id: i32,
-    pub text: String,
-    // Example: Add more fields like this:
-    // pub email: String,
-    // pub age: i32,
-    // pub active: bool,
-    // pub score: f64,
-}
+    pub fn update<T: Serialize>(&mut self, table: &str, record: &T) -> &mut Self {
+        self.push(serde_json::json!({ "action": "update", "table": table, "record": record }))
+    }
 
-// ============================================================================
-// CONVENIENCE FUNCTIONS
-// ============================================================================
+    pub fn update_many<T: Serialize>(&mut self, table: &str, records: &[T]) -> &mut Self {
+        self.push(serde_json::json!({ "action": "update_many", "table": table, "records": records }))
+    }
 
-#[allow(unused)]
-pub fn create_database_client() -> DatabaseClient {
-    DatabaseClient::new(WORKER_URL.to_string())
-}
+    pub fn update_column(&mut self, table: &str, id: i64, column: &str, value: &serde_json::Value) -> &mut Self {
+        self.push(serde_json::json!({
+            "action": "update_by_column",
+            "table": table,
+            "id": id,
+            "column": column,
+            "value": value
+        }))
+    }
+
+    pub fn delete(&mut self, table: &str, id: i64) -> &mut Self {
+        self.push(serde_json::json!({ "action": "delete", "table": table, "id": id }))
+    }
+
+    /// Only the filters of the query are used
+    pub fn delete_where(&mut self, table: &str, query: &Query) -> &mut Self {
+        self.push(serde_json::json!({ "action": "delete_where", "table": table, "where": query.filters }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
 
+    fn push(&mut self, operation: serde_json::Value) -> &mut Self {
+        self.operations.push(operation);
+        self
+    }
+}
 
 /// Create a table with custom name and schema
 /// The table name and columns are fully customizable
@@ -211,35 +1390,66 @@ pub fn create_database_client() -> DatabaseClient {
 /// )
 /// ```
 
+#[derive(Clone)]
 pub struct DatabaseClient {
-    worker_url: String,
+    backend: Rc<dyn DatabaseBackend>,
+    timeout: f64,                // Seconds before one attempt gives up
+    retries: u32,                // Extra attempts after network errors, timeouts and server errors
+    backoff: f64,                // Seconds to wait before the first retry, doubled each retry
+    max_backoff: f64,
+    cancel_count: Rc<Cell<u64>>, // Bumped by cancel_all(), shared with clones
+    offline: Option<Rc<RefCell<OfflineQueue>>>,
+    api_key: Option<String>,
+    token: Option<String>,
+    session: Rc<RefCell<Option<Session>>>, // The logged in player, shared with clones
+    cache: Option<Rc<RefCell<ResponseCache>>>,
 }
 
 impl DatabaseClient {
         #[allow(unused)]    
-        pub async fn insert_record<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, Box<dyn std::error::Error>> {
+        pub async fn insert_record<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, DbError> {
             let payload = serde_json::json!({
                 "action": "insert",
                 "table": table,
                 "record": record
             });
-            let resp = self.send_request(&payload).await?;
-            Ok(resp["id"].as_i64().unwrap_or(0))
+            let resp = self.send_write(&payload).await?;
+            resp["id"]
+                .as_i64()
+                .ok_or_else(|| DbError::Decode("insert response has no id".to_string()))
+        }
+
+        /// Insert many records in one request, returns the new ids in order
+        /// If one insert fails none of them are kept
+        #[allow(unused)]
+        pub async fn insert_many<T: Serialize>(&self, table: &str, records: &[T]) -> Result<Vec<i64>, DbError> {
+            let payload = serde_json::json!({
+                "action": "insert_many",
+                "table": table,
+                "records": records
+            });
+            let resp = self.send_write(&payload).await?;
+            let ids = resp["ids"]
+                .as_array()
+                .ok_or_else(|| DbError::Decode("insert_many response has no ids".to_string()))?;
+            ids.iter()
+                .map(|id| id.as_i64().ok_or_else(|| DbError::Decode(format!("invalid id in response: {}", id))))
+                .collect()
         }
 
         #[allow(unused)]
-        pub async fn update_record_by_struct<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, Box<dyn std::error::Error>> {
+        pub async fn update_record_by_struct<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, DbError> {
             let payload = serde_json::json!({
                 "action": "update",
                 "table": table,
                 "record": record
             });
-            let resp = self.send_request(&payload).await?;
+            let resp = self.send_write(&payload).await?;
             Ok(resp["updated"].as_i64().unwrap_or(0))
         }
 
         #[allow(unused)]
-        pub async fn update_record_by_id(&self, table: &str, id: i64, column: &str, value: &serde_json::Value) -> Result<i64, Box<dyn std::error::Error>> {
+        pub async fn update_record_by_id(&self, table: &str, id: i64, column: &str, value: &serde_json::Value) -> Result<i64, DbError> {
             let payload = serde_json::json!({
                 "action": "update_by_column",
                 "table": table,
@@ -247,126 +1457,505 @@ impl DatabaseClient {
                 "column": column,
                 "value": value
             });
-            let resp = self.send_request(&payload).await?;
+            let resp = self.send_write(&payload).await?;
+            Ok(resp["updated"].as_i64().unwrap_or(0))
+        }
+
+        /// Update many records (matched by id) in one request, all or nothing
+        #[allow(unused)]
+        pub async fn update_many<T: Serialize>(&self, table: &str, records: &[T]) -> Result<i64, DbError> {
+            let payload = serde_json::json!({
+                "action": "update_many",
+                "table": table,
+                "records": records
+            });
+            let resp = self.send_write(&payload).await?;
             Ok(resp["updated"].as_i64().unwrap_or(0))
         }
 
         #[allow(unused)]
-        pub async fn delete_record_by_id(&self, table: &str, id: i64) -> Result<i64, Box<dyn std::error::Error>> {
+        pub async fn delete_record_by_id(&self, table: &str, id: i64) -> Result<i64, DbError> {
             let payload = serde_json::json!({
                 "action": "delete",
                 "table": table,
                 "id": id
             });
-            let resp = self.send_request(&payload).await?;
+            let resp = self.send_write(&payload).await?;
             Ok(resp["deleted"].as_i64().unwrap_or(0))
         }
         
+        /// Delete every record matching the query's filters (order and paging are ignored)
+        /// At least one filter is required so a whole table can't be emptied by mistake
         #[allow(unused)]
-        async fn send_request(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-            let body = payload.to_string();
-            
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let url = &self.worker_url;
-                let response = ureq::post(url)
-                    .set("Content-Type", "application/json")
-                    .send_string(&body);
-                let text = match response {
-                    Ok(resp) => resp.into_string()?,
-                    Err(ureq::Error::Status(code, resp)) => {
-                        let err_body = resp.into_string().unwrap_or_else(|_| "Could not read error body".to_string());
-                        return Err(format!("HTTP {} error: {}", code, err_body).into());
-                    }
-                    Err(e) => return Err(e.into()),
-                };
-                let json: serde_json::Value = serde_json::from_str(&text)?;
-                return Ok(json);
+        pub async fn delete_where(&self, table: &str, query: &Query) -> Result<i64, DbError> {
+            if query.filters.is_empty() {
+                return Err(DbError::Invalid("delete_where needs at least one filter".to_string()));
             }
-            
-            #[cfg(target_arch = "wasm32")]
-            {
-                #[link(wasm_import_module = "env")]
-                extern "C" {
-                    fn mq_db_query(ptr: *const u8, len: usize, url_ptr: *const u8, url_len: usize);
-                    fn mq_db_query_result_len() -> usize;
-                    fn mq_db_query_fill_result(ptr: *mut u8);
-                    fn mq_db_query_clear_result();
-                }
-                
-                let url_bytes = self.worker_url.as_bytes();
-                let json_bytes = body.as_bytes();
-                // Call JS: mq_db_query(ptr, len, url_ptr, url_len)
-                unsafe {
-                    mq_db_query(
-                        json_bytes.as_ptr(),
-                        json_bytes.len(),
-                        url_bytes.as_ptr(),
-                        url_bytes.len(),
-                    );
-                }
-                let mut tries = 0;
-                let max_tries = 100;
-                let mut result_len = 0;
-                while tries < max_tries {
-                    result_len = unsafe { mq_db_query_result_len() };
-                    if result_len > 0 {
-                        break;
+            let payload = serde_json::json!({
+                "action": "delete_where",
+                "table": table,
+                "where": query.filters
+            });
+            let resp = self.send_write(&payload).await?;
+            Ok(resp["deleted"].as_i64().unwrap_or(0))
+        }
+
+        /// Run every change in the transaction in one request, all or nothing
+        /// Returns one result per change, in order
+        #[allow(unused)]
+        pub async fn run_transaction(&self, transaction: &Transaction) -> Result<Vec<serde_json::Value>, DbError> {
+            if transaction.is_empty() {
+                return Ok(Vec::new());
+            }
+            let payload = serde_json::json!({
+                "action": "transaction",
+                "operations": transaction.operations
+            });
+            let resp = self.send_write(&payload).await?;
+            Ok(resp["results"].as_array().cloned().unwrap_or_default())
+        }
+
+        // Send a request, retrying with a growing wait for errors that might go away
+        #[allow(unused)]
+        async fn send_request(&self, payload: &serde_json::Value) -> Result<serde_json::Value, DbError> {
+            let started_cancel_count = self.cancel_count.get();
+            let cancelled = || self.cancel_count.get() != started_cancel_count;
+
+            let mut delay = self.backoff;
+            let mut attempt = 0;
+            loop {
+                // Read every attempt, a login can finish while a request is waiting to retry
+                let token = self.session.borrow().as_ref().map(|session| session.token.clone()).or_else(|| self.token.clone());
+                let context = RequestContext {
+                    deadline: now() + self.timeout,
+                    timeout: self.timeout,
+                    cancelled: &cancelled,
+                    api_key: self.api_key.as_deref(),
+                    token: token.as_deref(),
+                };
+                let result = self.backend.execute(payload, &context).await;
+
+                match result {
+                    Err(err) if err.is_retryable() && attempt < self.retries => {
+                        attempt += 1;
+                        let wait_until = now() + delay;
+                        while now() < wait_until {
+                            if cancelled() {
+                                return Err(DbError::Cancelled);
+                            }
+                            next_frame().await;
+                        }
+                        delay = (delay * 2.0).min(self.max_backoff);
                     }
-                    tries += 1;
-                    next_frame().await;
+                    result => return result,
                 }
-                if result_len == 0 {
-                    return Err("No result from JS db_query (timeout or JS error)".into());
-                }
-                let mut buf = vec![0u8; result_len];
-                unsafe {
-                    mq_db_query_fill_result(buf.as_mut_ptr());
-                    mq_db_query_clear_result();
+            }
+        }
+    pub fn new(worker_url: String) -> Self {
+        Self::from_backend(WorkerBackend::new(&worker_url))
+    }
+
+    /// A client that sends its requests to any backend instead of the worker
+    /// (see db_local.rs for SQLite and in-memory backends, db_providers.rs for Supabase and Firebase)
+    #[allow(unused)]
+    pub fn from_backend<B: DatabaseBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Rc::new(backend),
+            timeout: 10.0,
+            retries: 0,
+            backoff: 0.5,
+            max_backoff: 8.0,
+            cancel_count: Rc::new(Cell::new(0)),
+            offline: None,
+            api_key: None,
+            token: None,
+            session: Rc::new(RefCell::new(None)),
+            cache: None,
+        }
+    }
+
+    /// Seconds to wait for an answer before giving up with DbError::Timeout (default 10)
+    #[allow(unused)]
+    pub fn with_timeout(&mut self, seconds: f32) -> &mut Self {
+        self.timeout = seconds.max(0.0) as f64;
+        self
+    }
+
+    /// Try again this many times after a network error, timeout or server error (default 0)
+    /// Careful with inserts: if the first try reached the database but the answer was lost,
+    /// a retry inserts the record again
+    #[allow(unused)]
+    pub fn with_retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Seconds to wait before the first retry, doubled after each retry up to `max` (default 0.5 and 8)
+    #[allow(unused)]
+    pub fn with_backoff(&mut self, initial: f32, max: f32) -> &mut Self {
+        self.backoff = initial.max(0.0) as f64;
+        self.max_backoff = max.max(initial).max(0.0) as f64;
+        self
+    }
+
+    /// Keep writes made while offline in a queue saved at `path` (through TextFile, so
+    /// localStorage on the web) and send them later with sync()
+    /// Writes already queued by an earlier run are loaded
+    /// Careful with inserts: a write that timed out is queued and sent again, so if it had
+    /// reached the database (only the answer was lost) the record is inserted twice
+    #[allow(unused)]
+    pub fn with_offline_queue(&mut self, path: &str) -> Result<&mut Self, DbError> {
+        self.offline = Some(Rc::new(RefCell::new(OfflineQueue::load(path)?)));
+        Ok(self)
+    }
+
+    /// Seconds between sync attempts suggested by should_sync() (default 5)
+    #[allow(unused)]
+    pub fn with_sync_interval(&mut self, seconds: f32) -> &mut Self {
+        if let Some(queue) = &self.offline {
+            queue.borrow_mut().sync_interval = seconds.max(0.0) as f64;
+        }
+        self
+    }
+
+    /// Number of writes waiting to be sent
+    #[allow(unused)]
+    pub fn queued_writes(&self) -> usize {
+        self.offline.as_ref().map_or(0, |queue| queue.borrow().writes.len())
+    }
+
+    #[allow(unused)]
+    pub fn sync_status(&self) -> SyncStatus {
+        let Some(queue) = &self.offline else {
+            return SyncStatus::Synced;
+        };
+        let queue = queue.borrow();
+        match queue.writes.len() {
+            0 => SyncStatus::Synced,
+            count if queue.syncing => SyncStatus::Syncing(count),
+            count => SyncStatus::Waiting(count),
+        }
+    }
+
+    /// The error from the last sync attempt, if it failed
+    #[allow(unused)]
+    pub fn last_sync_error(&self) -> Option<DbError> {
+        self.offline.as_ref().and_then(|queue| queue.borrow().last_error.clone())
+    }
+
+    /// True when writes are waiting, no sync is running, and the sync interval has passed
+    #[allow(unused)]
+    pub fn should_sync(&self) -> bool {
+        self.offline.as_ref().is_some_and(|queue| {
+            let queue = queue.borrow();
+            !queue.writes.is_empty() && !queue.syncing && now() - queue.last_attempt >= queue.sync_interval
+        })
+    }
+
+    /// Send the queued writes in the order they were made, returns how many were sent
+    /// Stops (keeping the rest) at the first network error. A write the worker rejects
+    /// (for example a bad column name) is dropped so it can't block the queue forever.
+    #[allow(unused)]
+    pub async fn sync(&self) -> Result<usize, DbError> {
+        let Some(queue) = &self.offline else {
+            return Ok(0);
+        };
+        {
+            let mut queue = queue.borrow_mut();
+            if queue.syncing {
+                return Ok(0);
+            }
+            queue.syncing = true;
+            queue.last_attempt = now();
+            queue.last_error = None;
+        }
+        let _guard = SyncGuard(queue);
+
+        let mut sent = 0;
+        loop {
+            let Some(write) = queue.borrow().writes.first().cloned() else {
+                return Ok(sent);
+            };
+
+            let result = self.send_request(&write.payload).await;
+            // Answers read while the write was queued or on its way are from before it
+            self.invalidate_written(&write.payload);
+            if let Err(err) = &result {
+                queue.borrow_mut().last_error = Some(err.clone());
+                if err.is_retryable() || *err == DbError::Cancelled {
+                    return Err(err.clone());
                 }
-                let text = String::from_utf8(buf).map_err(|e| format!("UTF-8 error: {}", e))?;
-                let json: serde_json::Value = serde_json::from_str(&text)?;
-                return Ok(json);
             }
-            
-            #[cfg(not(any(target_arch = "wasm32", not(target_arch = "wasm32"))))]
-            {
-                unreachable!("This should never be reached");
+
+            let mut queue = queue.borrow_mut();
+            queue.writes.remove(0);
+            queue.save()?;
+            if result.is_ok() {
+                sent += 1;
             }
         }
-    pub fn new(worker_url: String) -> Self {
-        Self { worker_url }
+    }
+
+    // Send a write, or queue it if the worker can't be reached (or older writes are still waiting)
+    async fn send_write(&self, payload: &serde_json::Value) -> Result<serde_json::Value, DbError> {
+        self.invalidate_written(payload);
+
+        let Some(queue) = &self.offline else {
+            let result = self.send_request(payload).await;
+            // A read sent while the write was on its way may have stored the old rows
+            self.invalidate_written(payload);
+            return result;
+        };
+
+        // Writes must reach the database in order, so queue behind anything still waiting
+        if queue.borrow().writes.is_empty() {
+            let result = self.send_request(payload).await;
+            self.invalidate_written(payload);
+            match result {
+                Err(DbError::Network(_)) | Err(DbError::Timeout) => {}
+                result => return result,
+            }
+        }
+
+        let mut queue = queue.borrow_mut();
+        queue.writes.push(QueuedWrite {
+            payload: payload.clone(),
+            queued_at: now(),
+        });
+        queue.save()?;
+        Err(DbError::Queued)
+    }
+
+    // Marks the cached answers for the tables a write changes as old
+    fn invalidate_written(&self, payload: &serde_json::Value) {
+        if let Some(cache) = &self.cache {
+            let mut cache = cache.borrow_mut();
+            cache.invalidate(payload["table"].as_str().unwrap_or_default());
+            for op in payload["operations"].as_array().into_iter().flatten() {
+                cache.invalidate(op["table"].as_str().unwrap_or_default());
+            }
+        }
+    }
+
+    /// Keep fetch_table and fetch_record_by_id answers for `seconds` and answer from them
+    /// instead of asking again, the last answer is also used when the worker can't be reached
+    #[allow(unused)]
+    pub fn with_cache(&mut self, seconds: f32) -> &mut Self {
+        match &self.cache {
+            Some(cache) => cache.borrow_mut().ttl = seconds.max(0.0) as f64,
+            None => {
+                self.cache = Some(Rc::new(RefCell::new(ResponseCache {
+                    path: None,
+                    ttl: seconds.max(0.0) as f64,
+                    entries: Vec::new(),
+                    writes: 0,
+                })))
+            }
+        }
+        self
+    }
+
+    /// Save the cache at `path` (through TextFile) and load what was saved last run
+    /// Turns the cache on with a 60 second limit if with_cache wasn't called first
+    #[allow(unused)]
+    pub fn with_cache_file(&mut self, path: &str) -> Result<&mut Self, DbError> {
+        if self.cache.is_none() {
+            self.with_cache(60.0);
+        }
+        if let Some(cache) = &self.cache {
+            cache.borrow_mut().load(path)?;
+        }
+        Ok(self)
+    }
+
+    /// The last fetch_table answer for a table, however old, without a request
+    #[allow(unused)]
+    pub fn cached_table<T: for<'de> Deserialize<'de>>(&self, table: &str) -> Option<Vec<T>> {
+        let cache = self.cache.as_ref()?.borrow();
+        let response = &cache.get(table, None)?.response;
+        records_from(response).ok()
+    }
+
+    /// The last fetch_record_by_id answer for a record, however old, without a request
+    #[allow(unused)]
+    pub fn cached_record_by_id<T: for<'de> Deserialize<'de>>(&self, table: &str, id: i64) -> Option<T> {
+        let cache = self.cache.as_ref()?.borrow();
+        let response = &cache.get(table, Some(id))?.response;
+        record_from(response).ok().flatten()
+    }
+
+    /// Seconds since fetch_table(table) was cached, None if it isn't
+    #[allow(unused)]
+    pub fn cache_age(&self, table: &str) -> Option<f32> {
+        let cache = self.cache.as_ref()?.borrow();
+        cache.get(table, None).map(|entry| (now() - entry.stored_at) as f32)
+    }
+
+    /// Make the next read of a table ask the worker (for changes made outside this client)
+    #[allow(unused)]
+    pub fn invalidate_cache(&self, table: &str) {
+        if let Some(cache) = &self.cache {
+            cache.borrow_mut().invalidate(table);
+        }
+    }
+
+    #[allow(unused)]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            let mut cache = cache.borrow_mut();
+            cache.entries.clear();
+            let _ = cache.save();
+        }
+    }
+
+    // A read that can be answered from the cache
+    async fn send_cached(&self, table: &str, id: Option<i64>, payload: &serde_json::Value) -> Result<serde_json::Value, DbError> {
+        let Some(cache) = &self.cache else {
+            return self.send_request(payload).await;
+        };
+        if let Some(response) = cache.borrow().fresh(table, id) {
+            return Ok(response);
+        }
+
+        let writes = cache.borrow().writes;
+        match self.send_request(payload).await {
+            Ok(response) => {
+                cache.borrow_mut().store(table, id, &response, writes);
+                Ok(response)
+            }
+            // Offline, the last answer is better than nothing
+            Err(err) if err.is_retryable() => match cache.borrow().get(table, id) {
+                Some(entry) => Ok(entry.response.clone()),
+                None => Err(err),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Send this key with every request (the worker's API_KEY, or db_server's --api-key)
+    #[allow(unused)]
+    pub fn with_api_key(&mut self, key: &str) -> &mut Self {
+        self.api_key = Some(key.to_string());
+        self
+    }
+
+    /// Send a fixed bearer token with every request
+    /// While a player is logged in their session token is sent instead
+    #[allow(unused)]
+    pub fn with_token(&mut self, token: &str) -> &mut Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Make a new player account and log in as it
+    #[allow(unused)]
+    pub async fn register(&self, username: &str, password: &str) -> Result<Session, DbError> {
+        self.start_session("register", username, password).await
+    }
+
+    /// Log in, every request after this is sent as the player
+    #[allow(unused)]
+    pub async fn login(&self, username: &str, password: &str) -> Result<Session, DbError> {
+        self.start_session("login", username, password).await
+    }
+
+    /// Log out on the server and forget the session (it is forgotten even if the server can't be reached)
+    #[allow(unused)]
+    pub async fn logout(&self) -> Result<(), DbError> {
+        if self.session.borrow().is_none() {
+            return Ok(());
+        }
+        let result = self.send_request(&serde_json::json!({ "action": "logout" })).await;
+        self.session.replace(None);
+        result.map(|_| ())
+    }
+
+    /// The logged in player, save it (for example with TextFile::save_json) to stay logged in next time
+    #[allow(unused)]
+    pub fn session(&self) -> Option<Session> {
+        self.session.borrow().clone()
+    }
+
+    /// Use a session saved from an earlier run, or None to forget it without telling the server
+    #[allow(unused)]
+    pub fn set_session(&self, session: Option<Session>) {
+        self.session.replace(session);
+    }
+
+    #[allow(unused)]
+    pub fn player_id(&self) -> Option<i64> {
+        self.session.borrow().as_ref().map(|session| session.player_id)
+    }
+
+    async fn start_session(&self, action: &str, username: &str, password: &str) -> Result<Session, DbError> {
+        let payload = serde_json::json!({
+            "action": action,
+            "username": username,
+            "password": password
+        });
+        let session: Session = serde_json::from_value(self.send_request(&payload).await?)?;
+        self.session.replace(Some(session.clone()));
+        Ok(session)
+    }
+
+    /// Stop every request from this client (and its clones) that is still running,
+    /// they finish with DbError::Cancelled. Requests started afterwards are not affected.
+    #[allow(unused)]
+    pub fn cancel_all(&self) {
+        self.cancel_count.set(self.cancel_count.get() + 1);
     }
 
 
    
+    /// Make the table for a DbRecord struct if it isn't there yet
+    /// An existing table is left alone, even if the struct has changed since it was made
+    #[allow(unused)]
+    pub async fn create_table_if_missing<T: DbRecord>(&self) -> Result<(), DbError> {
+        let payload = serde_json::json!({
+            "action": "create_table",
+            "table": T::table_name(),
+            "columns": T::columns(),
+            "unique": T::unique_columns()
+        });
+        self.send_request(&payload).await?;
+        Ok(())
+    }
+
     #[allow(unused)]
-    pub async fn fetch_table<T: for<'de> Deserialize<'de>>(&self, table: &str) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    pub async fn fetch_table<T: for<'de> Deserialize<'de>>(&self, table: &str) -> Result<Vec<T>, DbError> {
         let payload = serde_json::json!({
             "action": "fetch",
             "table": table
         });
+        let resp = self.send_cached(table, None, &payload).await?;
+        records_from(&resp)
+    }
+    #[allow(unused)]
+    pub async fn fetch_query<T: for<'de> Deserialize<'de>>(&self, table: &str, query: &Query) -> Result<Vec<T>, DbError> {
+        let mut payload = serde_json::to_value(query)?;
+        payload["action"] = "query".into();
+        payload["table"] = table.into();
         let resp = self.send_request(&payload).await?;
-        let records = resp["records"].as_array().cloned().unwrap_or_default();
-        let mut result = Vec::new();
-        for record in records {
-            result.push(serde_json::from_value(record)?);
-        }
-        Ok(result)
+        records_from(&resp)
+    }
+    /// How many rows match the query's filters (its sorting and paging are ignored)
+    #[allow(unused)]
+    pub async fn count_query(&self, table: &str, query: &Query) -> Result<i64, DbError> {
+        let mut payload = serde_json::to_value(query)?;
+        payload["action"] = "count".into();
+        payload["table"] = table.into();
+        let resp = self.send_request(&payload).await?;
+        resp["count"].as_i64().ok_or_else(|| DbError::Decode("Missing count".to_string()))
     }
     #[allow(unused)]
-    pub async fn fetch_record_by_id<T: for<'de> Deserialize<'de>>(&self, table: &str, id: i64) -> Result<Option<T>, Box<dyn std::error::Error>> {
+    pub async fn fetch_record_by_id<T: for<'de> Deserialize<'de>>(&self, table: &str, id: i64) -> Result<Option<T>, DbError> {
         let payload = serde_json::json!({
             "action": "fetch_by_id",
             "table": table,
             "id": id
         });
-        let resp = self.send_request(&payload).await?;
-        let record = resp.get("record").cloned();
-        match record {
-            Some(val) if !val.is_null() => Ok(Some(serde_json::from_value(val)?)),
-            _ => Ok(None)
-        }
+        let resp = self.send_cached(table, Some(id), &payload).await?;
+        record_from(&resp)
     }
 
 
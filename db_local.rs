@@ -0,0 +1,1092 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Local backends for DatabaseClient, so a game can be built and tested
+with no network and no Cloudflare worker. Every DatabaseClient method (fetch_table,
+insert_record, fetch_query, run_transaction, ...) works the same as with the worker.
+    - MemoryBackend: keeps tables in memory, works on desktop and web (good for tests)
+    - SqliteBackend: an SQLite file on desktop (not available on the web)
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod database;
+    pub mod db_local;
+
+For SqliteBackend only, add rusqlite with the following command in the terminal:
+    cargo add rusqlite@0.32 --features bundled,column_decltype --optional --target 'cfg(not(target_arch = "wasm32"))'
+
+In the Cargo.toml file add sqlite to the features section like this:
+    [features]
+    default = ["sqlite"]
+    sqlite = ["dep:rusqlite"]
+
+Then add the following with the use commands:
+    use crate::utils::db_local::{create_memory_client, create_sqlite_client};
+
+Then above the loop you would use:
+    // Tables in memory, they are made by create_table_if_missing or the first insert
+    let client = create_memory_client();
+
+    // Or an SQLite file, create your tables once with execute_sql
+    let client = create_sqlite_client("game.db", "
+        CREATE TABLE IF NOT EXISTS messages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            text TEXT NOT NULL
+        );
+    ").unwrap();
+
+    // Everything else is the same as with the worker
+    let id = client.insert_record("messages", &new_record).await?;
+    let records: Vec<DatabaseTable> = client.fetch_table("messages").await?;
+
+To use the worker for the real game and a local database while developing:
+    #[cfg(debug_assertions)]
+    let client = create_sqlite_client("dev.db", SCHEMA).unwrap();
+    #[cfg(not(debug_assertions))]
+    let client = create_database_client();
+
+Using a backend directly (for example to share one between clients or run setup SQL):
+    use crate::utils::database::DatabaseClient;
+    use crate::utils::db_local::{MemoryBackend, SqliteBackend};
+
+    let backend = SqliteBackend::open("game.db").unwrap();   // SqliteBackend::open_in_memory() for a throwaway one
+    backend.execute_sql("CREATE TABLE IF NOT EXISTS scores (id INTEGER PRIMARY KEY AUTOINCREMENT, score INTEGER)").unwrap();
+    let client = DatabaseClient::from_backend(backend);
+
+Note: Errors match what the worker would give, a bad request is DbError::Http with
+status 400, a row that breaks a UNIQUE constraint has status 409 and a database error
+(like a missing table) is DbError::Http with status 500.
+MemoryBackend doesn't check columns, any record can go into any table.
+*/
+
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::future;
+
+use crate::utils::database::{
+    bad_request, column_update, identifier, record_fields, request_action, required_id, transaction_operations, update_fields,
+    DatabaseBackend, DatabaseClient, DbError, RequestContext, RequestFuture,
+};
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+use crate::utils::database::safe_identifier;
+
+/// A DatabaseClient with its tables kept in memory
+#[allow(unused)]
+pub fn create_memory_client() -> DatabaseClient {
+    DatabaseClient::from_backend(MemoryBackend::new())
+}
+
+/// A DatabaseClient using the SQLite file at `path` (made if it doesn't exist)
+/// `schema` is run first, use CREATE TABLE IF NOT EXISTS so it can run every time
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+#[allow(unused)]
+pub fn create_sqlite_client(path: &str, schema: &str) -> Result<DatabaseClient, DbError> {
+    let backend = SqliteBackend::open(path)?;
+    backend.execute_sql(schema)?;
+    Ok(DatabaseClient::from_backend(backend))
+}
+
+// ============================================================================
+// REQUEST HELPERS (shared by both backends)
+// ============================================================================
+
+// The worker refuses things a player isn't allowed to do with status 403
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn forbidden(message: &str) -> DbError {
+    DbError::Http {
+        status: 403,
+        body: message.to_string(),
+    }
+}
+
+// A database error is answered with status 500
+fn db_error(message: &str) -> DbError {
+    DbError::Http {
+        status: 500,
+        body: format!("DB error: {}", message),
+    }
+}
+
+// except a row that breaks a UNIQUE constraint, which is refused with status 409
+fn statement_error(message: &str) -> DbError {
+    if !message.contains("UNIQUE constraint failed") {
+        return db_error(message);
+    }
+    DbError::Http {
+        status: 409,
+        body: format!("DB error: {}", message),
+    }
+}
+
+// Column definitions for create_table ("name TYPE NOT NULL"), after the id column
+fn column_definitions(data: &Value) -> Result<Vec<String>, DbError> {
+    let columns = data["columns"].as_array().ok_or_else(|| bad_request("Missing columns"))?;
+    let mut definitions = Vec::new();
+    for column in columns {
+        let name = identifier(&column["name"])?;
+        let sql_type = column["type"].as_str().unwrap_or_default();
+        if name == "id" || !matches!(sql_type, "INTEGER" | "REAL" | "TEXT" | "BOOLEAN") {
+            return Err(bad_request("Invalid column"));
+        }
+        let not_null = if column["nullable"].as_bool().unwrap_or(false) { "" } else { " NOT NULL" };
+        definitions.push(format!("{} {}{}", name, sql_type, not_null));
+    }
+    let unique = unique_columns(data)?;
+    if !unique.is_empty() {
+        definitions.push(format!("UNIQUE({})", unique.join(", ")));
+    }
+    Ok(definitions)
+}
+
+// Columns that together can only be on one row, each must be one of the columns
+fn unique_columns(data: &Value) -> Result<Vec<&str>, DbError> {
+    let columns = data["columns"].as_array().ok_or_else(|| bad_request("Missing columns"))?;
+    let mut unique = Vec::new();
+    for name in data["unique"].as_array().into_iter().flatten() {
+        if !columns.iter().any(|column| column["name"] == *name) {
+            return Err(bad_request("Invalid unique columns"));
+        }
+        unique.push(identifier(name)?);
+    }
+    Ok(unique)
+}
+
+// Filters from a Query, checked before anything runs
+fn filters(data: &Value) -> Result<Vec<(&str, &str, &Value)>, DbError> {
+    let mut filters = Vec::new();
+    for filter in data["where"].as_array().into_iter().flatten() {
+        let op = filter["op"].as_str().unwrap_or_default();
+        if !matches!(op, "=" | "!=" | "<" | "<=" | ">" | ">=" | "like") {
+            return Err(bad_request("Invalid operator"));
+        }
+        filters.push((identifier(&filter["column"])?, op, &filter["value"]));
+    }
+    Ok(filters)
+}
+
+// ============================================================================
+// MEMORY BACKEND
+// ============================================================================
+
+type Row = Map<String, Value>;
+
+#[derive(Clone, Default)]
+struct MemoryTable {
+    last_id: i64,               // Ids are never reused, like AUTOINCREMENT
+    rows: BTreeMap<i64, Row>,   // By id, so rows come back in id order
+    unique: Vec<String>,        // Columns that together can only be on one row
+}
+
+/// Tables kept in memory, nothing is saved when the game closes
+/// Tables are made by create_table_if_missing or the first time a record is inserted,
+/// reading a table that doesn't exist gives no records
+#[derive(Default)]
+pub struct MemoryBackend {
+    tables: RefCell<BTreeMap<String, MemoryTable>>,
+}
+
+#[allow(unused)]
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove every table
+    pub fn clear(&self) {
+        self.tables.borrow_mut().clear();
+    }
+
+    /// Answer one worker protocol request
+    pub fn handle(&self, data: &Value) -> Result<Value, DbError> {
+        let action = request_action(data)?;
+        let mut tables = self.tables.borrow_mut();
+        let tables = &mut *tables;
+
+        match action {
+            "fetch" => Ok(json!({ "records": select(tables, data, false)? })),
+            "query" => Ok(json!({ "records": select(tables, data, true)? })),
+            "count" => {
+                let table = identifier(&data["table"])?;
+                let filters = filters(data)?;
+                let count = tables.get(table).map_or(0, |table| table.rows.values().filter(|row| matches_filters(row, &filters)).count());
+                Ok(json!({ "count": count }))
+            }
+            "fetch_by_id" => {
+                let table = identifier(&data["table"])?;
+                let record = data["id"]
+                    .as_i64()
+                    .and_then(|id| tables.get(table)?.rows.get(&id).cloned())
+                    .map_or(Value::Null, Value::Object);
+                Ok(json!({ "record": record }))
+            }
+            "create_table" => {
+                column_definitions(data)?;
+                let unique = unique_columns(data)?.into_iter().map(String::from).collect();
+                // Like CREATE TABLE IF NOT EXISTS, a table that is already there is left alone
+                tables.entry(identifier(&data["table"])?.to_string()).or_insert_with(|| MemoryTable {
+                    unique,
+                    ..Default::default()
+                });
+                Ok(json!({ "success": true }))
+            }
+            "transaction" => {
+                let operations = transaction_operations(data)?;
+                all_or_nothing(tables, |tables| {
+                    let results = operations.iter().map(|op| write(tables, op)).collect::<Result<Vec<_>, _>>()?;
+                    Ok(json!({ "results": results }))
+                })
+            }
+            _ => all_or_nothing(tables, |tables| write(tables, data)),
+        }
+    }
+}
+
+impl DatabaseBackend for MemoryBackend {
+    fn execute<'a>(&'a self, payload: &'a Value, _context: &'a RequestContext<'a>) -> RequestFuture<'a, Value> {
+        Box::pin(future::ready(self.handle(payload)))
+    }
+}
+
+// Put the tables back the way they were if anything fails
+fn all_or_nothing(
+    tables: &mut BTreeMap<String, MemoryTable>,
+    change: impl FnOnce(&mut BTreeMap<String, MemoryTable>) -> Result<Value, DbError>,
+) -> Result<Value, DbError> {
+    let backup = tables.clone();
+    let result = change(tables);
+    if result.is_err() {
+        *tables = backup;
+    }
+    result
+}
+
+fn write(tables: &mut BTreeMap<String, MemoryTable>, op: &Value) -> Result<Value, DbError> {
+    let table_name = identifier(&op["table"])?;
+    let table = tables.entry(table_name.to_string()).or_default();
+
+    match op["action"].as_str().unwrap_or_default() {
+        "insert" => {
+            if !op["record"].is_object() {
+                return Err(bad_request("Missing record"));
+            }
+            let id = insert_row(table_name, table, &op["record"])?;
+            Ok(json!({ "success": true, "id": id }))
+        }
+        "insert_many" => {
+            let records = op["records"].as_array().ok_or_else(|| bad_request("Missing records"))?;
+            let ids = records.iter().map(|record| insert_row(table_name, table, record)).collect::<Result<Vec<_>, _>>()?;
+            Ok(json!({ "inserted": ids.len(), "ids": ids }))
+        }
+        "update" => {
+            if op["record"]["id"].is_null() {
+                return Err(bad_request("Missing record or id"));
+            }
+            Ok(json!({ "updated": update_row(table_name, table, &op["record"])? }))
+        }
+        "update_many" => {
+            let records = op["records"].as_array().ok_or_else(|| bad_request("Missing records"))?;
+            if records.iter().any(|record| record["id"].is_null()) {
+                return Err(bad_request("Missing id in records"));
+            }
+            let mut updated = 0;
+            for record in records {
+                updated += update_row(table_name, table, record)?;
+            }
+            Ok(json!({ "updated": updated }))
+        }
+        "update_by_column" => {
+            let (id, column) = column_update(op)?;
+            let updated = match table.rows.get_mut(&id) {
+                Some(row) => {
+                    row.insert(column.to_string(), op["value"].clone());
+                    1
+                }
+                None => 0,
+            };
+            check_unique(table_name, table, id)?;
+            Ok(json!({ "updated": updated }))
+        }
+        "delete" => {
+            let id = required_id(op, "Missing id")?;
+            Ok(json!({ "deleted": table.rows.remove(&id).map_or(0, |_| 1) }))
+        }
+        "delete_where" => {
+            // Never delete a whole table by accident
+            if op["where"].as_array().is_none_or(|filters| filters.is_empty()) {
+                return Err(bad_request("Missing where"));
+            }
+            let filters = filters(op)?;
+            let before = table.rows.len();
+            table.rows.retain(|_, row| !matches_filters(row, &filters));
+            Ok(json!({ "deleted": before - table.rows.len() }))
+        }
+        _ => Err(bad_request("Unknown action")),
+    }
+}
+
+fn insert_row(table_name: &str, table: &mut MemoryTable, record: &Value) -> Result<i64, DbError> {
+    let mut row = Row::new();
+    for (key, value) in record_fields(record)? {
+        row.insert(key.to_string(), value.clone());
+    }
+    table.last_id += 1;
+    row.insert("id".to_string(), json!(table.last_id));
+    table.rows.insert(table.last_id, row);
+    check_unique(table_name, table, table.last_id)?;
+    Ok(table.last_id)
+}
+
+// Returns how many rows changed (0 or 1)
+fn update_row(table_name: &str, table: &mut MemoryTable, record: &Value) -> Result<i64, DbError> {
+    let fields = update_fields(record)?;
+    let Some(id) = record["id"].as_i64() else {
+        return Ok(0);
+    };
+    let Some(row) = table.rows.get_mut(&id) else {
+        return Ok(0);
+    };
+    for (key, value) in fields {
+        row.insert(key.to_string(), value.clone());
+    }
+    check_unique(table_name, table, id)?;
+    Ok(1)
+}
+
+// Fails if the row with `id` has the same unique columns as another row
+// Like SQL, rows with a NULL in those columns never clash (all_or_nothing undoes the write)
+fn check_unique(table_name: &str, table: &MemoryTable, id: i64) -> Result<(), DbError> {
+    let Some(row) = table.rows.get(&id).filter(|_| !table.unique.is_empty()) else {
+        return Ok(());
+    };
+    let same = |other: &Row| {
+        table.unique.iter().all(|column| {
+            let (a, b) = (row.get(column).unwrap_or(&Value::Null), other.get(column).unwrap_or(&Value::Null));
+            !a.is_null() && !b.is_null() && sql_order(a, b) == Ordering::Equal
+        })
+    };
+    if table.rows.iter().any(|(other_id, other)| *other_id != id && same(other)) {
+        let columns: Vec<String> = table.unique.iter().map(|column| format!("{}.{}", table_name, column)).collect();
+        return Err(statement_error(&format!("UNIQUE constraint failed: {}", columns.join(", "))));
+    }
+    Ok(())
+}
+
+// fetch gives every row, query also filters, sorts and pages like the SQL the worker builds
+fn select(tables: &BTreeMap<String, MemoryTable>, data: &Value, with_query: bool) -> Result<Vec<Value>, DbError> {
+    let table = identifier(&data["table"])?;
+    let Some(table) = tables.get(table) else {
+        return Ok(Vec::new());
+    };
+    let mut rows: Vec<&Row> = table.rows.values().collect();
+    if !with_query {
+        return Ok(rows.into_iter().cloned().map(Value::Object).collect());
+    }
+
+    let filters = filters(data)?;
+    rows.retain(|row| matches_filters(row, &filters));
+
+    let mut sorts = Vec::new();
+    for sort in data["order_by"].as_array().into_iter().flatten() {
+        sorts.push((identifier(&sort["column"])?, sort["descending"].as_bool().unwrap_or(false)));
+    }
+    rows.sort_by(|a, b| {
+        sorts.iter().fold(Ordering::Equal, |order, (column, descending)| {
+            order.then_with(|| {
+                let order = sql_order(a.get(*column).unwrap_or(&Value::Null), b.get(*column).unwrap_or(&Value::Null));
+                if *descending { order.reverse() } else { order }
+            })
+        })
+    });
+
+    let offset = data["offset"].as_u64().unwrap_or(0) as usize;
+    let limit = data["limit"].as_u64().map_or(usize::MAX, |limit| limit as usize);
+    Ok(rows.into_iter().skip(offset).take(limit).cloned().map(Value::Object).collect())
+}
+
+fn matches_filters(row: &Row, filters: &[(&str, &str, &Value)]) -> bool {
+    filters.iter().all(|(column, op, value)| {
+        let cell = row.get(*column).unwrap_or(&Value::Null);
+        // Like SQL, nothing matches NULL
+        if cell.is_null() || value.is_null() {
+            return false;
+        }
+        let order = sql_order(cell, value);
+        match *op {
+            "=" => order == Ordering::Equal,
+            "!=" => order != Ordering::Equal,
+            "<" => order == Ordering::Less,
+            "<=" => order != Ordering::Greater,
+            ">" => order == Ordering::Greater,
+            ">=" => order != Ordering::Less,
+            _ => like(&sql_text(cell), &sql_text(value)),
+        }
+    })
+}
+
+// SQLite's order: NULL, then numbers (true/false count as 1/0), then text
+fn sql_order(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) | Value::Number(_) => 1,
+            _ => 2,
+        }
+    }
+    fn number(value: &Value) -> f64 {
+        match value {
+            Value::Bool(b) => *b as i32 as f64,
+            _ => value.as_f64().unwrap_or(0.0),
+        }
+    }
+
+    match (rank(a), rank(b)) {
+        (1, 1) => number(a).total_cmp(&number(b)),
+        (2, 2) => sql_text(a).cmp(&sql_text(b)),
+        (rank_a, rank_b) => rank_a.cmp(&rank_b),
+    }
+}
+
+fn sql_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Bool(b) => (*b as i32).to_string(),
+        _ => value.to_string(),
+    }
+}
+
+// SQL LIKE: % is any run of characters, _ is one character, letters ignore case
+fn like(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+
+    // matches[i] is true when the pattern so far matches the first i characters of text
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+    for p in pattern {
+        matches = match p {
+            '%' => {
+                let mut next = matches.clone();
+                for i in 1..next.len() {
+                    next[i] = next[i] || next[i - 1];
+                }
+                next
+            }
+            _ => {
+                let mut next = vec![false; text.len() + 1];
+                for i in 0..text.len() {
+                    next[i + 1] = matches[i] && (p == '_' || p == text[i]);
+                }
+                next
+            }
+        };
+    }
+    matches[text.len()]
+}
+
+// ============================================================================
+// SQLITE BACKEND (desktop only)
+// ============================================================================
+
+// One SQL statement and its values
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+struct Statement {
+    sql: String,
+    params: Vec<rusqlite::types::Value>,
+}
+
+// What running one statement gave back
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+struct StepResult {
+    records: Vec<Value>,
+    affected: i64,
+    last_id: i64,
+}
+
+/// Who a request to SqliteBackend::handle_as comes from (see db_server.rs)
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Caller {
+    Guest,       // Not logged in
+    Player(i64), // Logged in, with their player id
+}
+
+/// An SQLite database file, runs the same SQL the worker sends to Turso
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+pub struct SqliteBackend {
+    connection: rusqlite::Connection,
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+#[allow(unused)]
+impl SqliteBackend {
+    /// Open (or make) the database file at `path`
+    pub fn open(path: &str) -> Result<Self, DbError> {
+        let connection = rusqlite::Connection::open(path).map_err(|e| db_error(&e.to_string()))?;
+        Ok(Self { connection })
+    }
+
+    /// A database that only lives in memory, good for tests
+    pub fn open_in_memory() -> Result<Self, DbError> {
+        let connection = rusqlite::Connection::open_in_memory().map_err(|e| db_error(&e.to_string()))?;
+        Ok(Self { connection })
+    }
+
+    /// Run SQL directly, for example CREATE TABLE statements (separate several with ;)
+    pub fn execute_sql(&self, sql: &str) -> Result<(), DbError> {
+        self.connection.execute_batch(sql).map_err(|e| db_error(&e.to_string()))
+    }
+
+    /// The SQLite connection, for anything the worker protocol can't do
+    pub fn connection(&self) -> &rusqlite::Connection {
+        &self.connection
+    }
+
+    /// Answer one worker protocol request, with full access to every table
+    pub fn handle(&self, data: &Value) -> Result<Value, DbError> {
+        self.handle_request(data, None)
+    }
+
+    /// Answer a request the way the worker does for someone over the network:
+    /// tables starting with _ (like db_server's accounts) can't be used, and tables with
+    /// an owner_id column can only be changed by the player who inserted each row
+    pub fn handle_as(&self, data: &Value, caller: Caller) -> Result<Value, DbError> {
+        self.handle_request(data, Some(caller))
+    }
+
+    fn handle_request(&self, data: &Value, caller: Option<Caller>) -> Result<Value, DbError> {
+        let action = request_action(data)?;
+        let table = data["table"].as_str().map(safe_identifier).transpose()?;
+        if let (Some(table), Some(_)) = (table, caller) {
+            if table.starts_with('_') {
+                return Err(forbidden("Table not allowed"));
+            }
+        }
+
+        // Every operation becomes one or more SQL statements.
+        // ops remembers which statements belong to which operation so the results can be added up.
+        let mut statements = Vec::new();
+        let mut ops = Vec::new();
+        match action {
+            "fetch" => statements.push(Statement {
+                sql: format!("SELECT * FROM {}", table.unwrap_or_default()),
+                params: Vec::new(),
+            }),
+            "fetch_by_id" => statements.push(Statement {
+                sql: format!("SELECT * FROM {} WHERE id = ?", table.unwrap_or_default()),
+                params: vec![sql_param(&data["id"])],
+            }),
+            "query" => statements.push(query_statement(data)?),
+            "count" => {
+                let mut params = Vec::new();
+                let sql = format!("SELECT COUNT(*) AS count FROM {}{}", table.unwrap_or_default(), where_clause(data, &mut params)?);
+                statements.push(Statement { sql, params });
+            }
+            "create_table" => statements.push(Statement {
+                sql: format!(
+                    "CREATE TABLE IF NOT EXISTS {} (id INTEGER PRIMARY KEY AUTOINCREMENT{})",
+                    table.unwrap_or_default(),
+                    column_definitions(data)?.iter().map(|column| format!(", {}", column)).collect::<String>()
+                ),
+                params: Vec::new(),
+            }),
+            "transaction" => {
+                for op in transaction_operations(data)? {
+                    let op_statements = write_statements(op, self.owner_for(op, caller)?)?;
+                    ops.push((op, statements.len(), op_statements.len()));
+                    statements.extend(op_statements);
+                }
+            }
+            _ => {
+                statements = write_statements(data, self.owner_for(data, caller)?)?;
+                ops.push((data, 0, statements.len()));
+            }
+        }
+
+        // More than one statement runs inside a transaction so it is all-or-nothing
+        let results = if statements.len() > 1 || action == "transaction" {
+            let transaction = self.connection.unchecked_transaction().map_err(|e| db_error(&e.to_string()))?;
+            let results = statements.iter().map(|statement| self.run(statement)).collect::<Result<Vec<_>, _>>()?;
+            transaction.commit().map_err(|e| db_error(&e.to_string()))?;
+            results
+        } else {
+            statements.iter().map(|statement| self.run(statement)).collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(match action {
+            "fetch" | "query" => json!({ "records": results[0].records }),
+            "fetch_by_id" => json!({ "record": results[0].records.first().cloned().unwrap_or(Value::Null) }),
+            "count" => json!({ "count": results[0].records.first().map_or(json!(0), |record| record["count"].clone()) }),
+            "create_table" => json!({ "success": true }),
+            "transaction" => json!({
+                "results": ops.iter().map(|(op, start, count)| op_result(op, &results[*start..start + count])).collect::<Vec<_>>()
+            }),
+            _ => op_result(data, &results),
+        })
+    }
+
+    // The player whose rows a write may change, None when any row may change
+    fn owner_for(&self, op: &Value, caller: Option<Caller>) -> Result<Option<i64>, DbError> {
+        let Some(caller) = caller else {
+            return Ok(None);
+        };
+        let table = identifier(&op["table"])?;
+        if table.starts_with('_') {
+            return Err(forbidden("Table not allowed"));
+        }
+
+        let owned = self
+            .connection
+            .prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = 'owner_id'")
+            .and_then(|mut statement| statement.exists([table]))
+            .map_err(|e| db_error(&e.to_string()))?;
+        match (owned, caller) {
+            (false, _) => Ok(None),
+            (true, Caller::Player(id)) => Ok(Some(id)),
+            (true, Caller::Guest) => Err(DbError::Http {
+                status: 401,
+                body: "Login required".to_string(),
+            }),
+        }
+    }
+
+    fn run(&self, statement: &Statement) -> Result<StepResult, DbError> {
+        let sql_error = |e: rusqlite::Error| statement_error(&e.to_string());
+        let mut prepared = self.connection.prepare(&statement.sql).map_err(sql_error)?;
+        let params = rusqlite::params_from_iter(statement.params.iter());
+
+        if prepared.column_count() == 0 {
+            let affected = prepared.execute(params).map_err(sql_error)? as i64;
+            return Ok(StepResult {
+                records: Vec::new(),
+                affected,
+                last_id: self.connection.last_insert_rowid(),
+            });
+        }
+
+        // Column names and declared types, so BOOLEAN columns come back as true/false
+        let columns: Vec<(String, bool)> = prepared
+            .columns()
+            .iter()
+            .map(|column| {
+                let is_bool = column.decl_type().is_some_and(|decl| decl.to_uppercase().contains("BOOL"));
+                (column.name().to_string(), is_bool)
+            })
+            .collect();
+
+        let mut rows = prepared.query(params).map_err(sql_error)?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().map_err(sql_error)? {
+            let mut record = Map::new();
+            for (i, (name, is_bool)) in columns.iter().enumerate() {
+                let value = match row.get_ref(i).map_err(sql_error)? {
+                    rusqlite::types::ValueRef::Null => Value::Null,
+                    rusqlite::types::ValueRef::Integer(n) if *is_bool => Value::Bool(n != 0),
+                    rusqlite::types::ValueRef::Integer(n) => json!(n),
+                    rusqlite::types::ValueRef::Real(n) => json!(n),
+                    rusqlite::types::ValueRef::Text(text) | rusqlite::types::ValueRef::Blob(text) => {
+                        Value::String(String::from_utf8_lossy(text).into_owned())
+                    }
+                };
+                record.insert(name.clone(), value);
+            }
+            records.push(Value::Object(record));
+        }
+        Ok(StepResult {
+            records,
+            affected: 0,
+            last_id: 0,
+        })
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+impl DatabaseBackend for SqliteBackend {
+    fn execute<'a>(&'a self, payload: &'a Value, _context: &'a RequestContext<'a>) -> RequestFuture<'a, Value> {
+        Box::pin(future::ready(self.handle(payload)))
+    }
+}
+
+// SQL statements for one write operation
+// With an owner, inserts are given that owner_id and only that owner's rows can change
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn write_statements(op: &Value, owner: Option<i64>) -> Result<Vec<Statement>, DbError> {
+    let table = identifier(&op["table"])?;
+
+    match op["action"].as_str().unwrap_or_default() {
+        "insert" => {
+            if !op["record"].is_object() {
+                return Err(bad_request("Missing record"));
+            }
+            Ok(vec![insert_statement(table, &op["record"], owner)?])
+        }
+        "insert_many" => {
+            let records = op["records"].as_array().ok_or_else(|| bad_request("Missing records"))?;
+            records.iter().map(|record| insert_statement(table, record, owner)).collect()
+        }
+        "update" => {
+            if op["record"]["id"].is_null() {
+                return Err(bad_request("Missing record or id"));
+            }
+            Ok(vec![update_statement(table, &op["record"], owner)?])
+        }
+        "update_many" => {
+            let records = op["records"].as_array().ok_or_else(|| bad_request("Missing records"))?;
+            if records.iter().any(|record| record["id"].is_null()) {
+                return Err(bad_request("Missing id in records"));
+            }
+            records.iter().map(|record| update_statement(table, record, owner)).collect()
+        }
+        "update_by_column" => {
+            let (id, column) = column_update(op)?;
+            if owner.is_some() && column == "owner_id" {
+                return Err(forbidden("owner_id can't be changed"));
+            }
+            let mut params = vec![sql_param(&op["value"]), id.into()];
+            let owner_check = owner_condition(owner, &mut params);
+            Ok(vec![Statement {
+                sql: format!("UPDATE {} SET {} = ? WHERE id = ?{}", table, column, owner_check),
+                params,
+            }])
+        }
+        "delete" => {
+            let id = required_id(op, "Missing id")?;
+            let mut params = vec![id.into()];
+            let owner_check = owner_condition(owner, &mut params);
+            Ok(vec![Statement {
+                sql: format!("DELETE FROM {} WHERE id = ?{}", table, owner_check),
+                params,
+            }])
+        }
+        "delete_where" => {
+            // Never delete a whole table by accident
+            if op["where"].as_array().is_none_or(|filters| filters.is_empty()) {
+                return Err(bad_request("Missing where"));
+            }
+            let mut params = Vec::new();
+            let mut sql = format!("DELETE FROM {}{}", table, where_clause(op, &mut params)?);
+            sql += owner_condition(owner, &mut params);
+            Ok(vec![Statement { sql, params }])
+        }
+        _ => Err(bad_request("Unknown action")),
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn insert_statement(table: &str, record: &Value, owner: Option<i64>) -> Result<Statement, DbError> {
+    let mut fields = record_fields(record)?;
+    let owner_id = owner.map(|id| json!(id));
+    if let Some(owner_id) = &owner_id {
+        fields.retain(|(key, _)| *key != "owner_id");
+        fields.push(("owner_id", owner_id));
+    }
+
+    let columns: Vec<&str> = fields.iter().map(|(key, _)| *key).collect();
+    let placeholders = vec!["?"; fields.len()].join(", ");
+    Ok(Statement {
+        sql: format!("INSERT INTO {} ({}) VALUES ({})", table, columns.join(", "), placeholders),
+        params: fields.iter().map(|(_, value)| sql_param(value)).collect(),
+    })
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn update_statement(table: &str, record: &Value, owner: Option<i64>) -> Result<Statement, DbError> {
+    let mut fields = update_fields(record)?;
+    let owner_id = owner.map(|id| json!(id));
+    if let Some(owner_id) = &owner_id {
+        fields.retain(|(key, _)| *key != "owner_id");
+        fields.push(("owner_id", owner_id));
+    }
+
+    let set_clause: Vec<String> = fields.iter().map(|(key, _)| format!("{} = ?", key)).collect();
+    let mut params: Vec<rusqlite::types::Value> = fields.iter().map(|(_, value)| sql_param(value)).collect();
+    params.push(sql_param(&record["id"]));
+    let owner_check = owner_condition(owner, &mut params);
+    Ok(Statement {
+        sql: format!("UPDATE {} SET {} WHERE id = ?{}", table, set_clause.join(", "), owner_check),
+        params,
+    })
+}
+
+// " AND owner_id = ?" when only the owner's rows may change
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn owner_condition(owner: Option<i64>, params: &mut Vec<rusqlite::types::Value>) -> &'static str {
+    match owner {
+        Some(id) => {
+            params.push(id.into());
+            " AND owner_id = ?"
+        }
+        None => "",
+    }
+}
+
+// Filters, sorting and paging from the Rust Query builder
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn query_statement(data: &Value) -> Result<Statement, DbError> {
+    let mut params = Vec::new();
+    let mut sql = format!("SELECT * FROM {}{}", identifier(&data["table"])?, where_clause(data, &mut params)?);
+
+    let mut sorts = Vec::new();
+    for sort in data["order_by"].as_array().into_iter().flatten() {
+        let direction = if sort["descending"].as_bool().unwrap_or(false) { "DESC" } else { "ASC" };
+        sorts.push(format!("{} {}", identifier(&sort["column"])?, direction));
+    }
+    if !sorts.is_empty() {
+        sql += &format!(" ORDER BY {}", sorts.join(", "));
+    }
+
+    if data["limit"].is_u64() || data["offset"].is_u64() {
+        sql += " LIMIT ?";
+        params.push(data["limit"].as_i64().unwrap_or(-1).into());
+    }
+    if let Some(offset) = data["offset"].as_i64() {
+        sql += " OFFSET ?";
+        params.push(offset.into());
+    }
+    Ok(Statement { sql, params })
+}
+
+// " WHERE a = ? AND b < ?" (or "" with no filters), adding the values to params
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn where_clause(data: &Value, params: &mut Vec<rusqlite::types::Value>) -> Result<String, DbError> {
+    let mut conditions = Vec::new();
+    for (column, op, value) in filters(data)? {
+        let op = if op == "like" { "LIKE" } else { op };
+        conditions.push(format!("{} {} ?", column, op));
+        params.push(sql_param(value));
+    }
+    Ok(if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    })
+}
+
+// What the Rust client gets back for one write operation
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn op_result(op: &Value, results: &[StepResult]) -> Value {
+    let affected: i64 = results.iter().map(|result| result.affected).sum();
+    match op["action"].as_str().unwrap_or_default() {
+        "insert" => json!({ "success": affected > 0, "id": results.first().map_or(0, |result| result.last_id) }),
+        "insert_many" => json!({ "inserted": affected, "ids": results.iter().map(|result| result.last_id).collect::<Vec<_>>() }),
+        "update" | "update_many" | "update_by_column" => json!({ "updated": affected }),
+        _ => json!({ "deleted": affected }),
+    }
+}
+
+// Convert JSON values to SQLite values (true/false become 1/0)
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+fn sql_param(value: &Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value as SqlValue;
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(n) => SqlValue::Integer(n),
+            None => SqlValue::Real(n.as_f64().unwrap_or(0.0)),
+        },
+        Value::String(text) => SqlValue::Text(text.clone()),
+        _ => SqlValue::Text(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Errors are compared by status, like a game would see them
+    fn answer(result: Result<Value, DbError>) -> Value {
+        match result {
+            Ok(value) => value,
+            Err(DbError::Http { status, .. }) => json!({ "error": status }),
+            Err(other) => json!({ "error": other.to_string() }),
+        }
+    }
+
+    // The same requests, in order, for every backend
+    fn script() -> Vec<Value> {
+        let player = |name: &str, score: f64, active: bool| json!({ "name": name, "score": score, "active": active });
+        vec![
+            json!({ "action": "create_table", "table": "players", "columns": [
+                { "name": "name", "type": "TEXT" },
+                { "name": "score", "type": "REAL", "nullable": true },
+                { "name": "active", "type": "BOOLEAN" }
+            ] }),
+            json!({ "action": "insert", "table": "players", "record": player("Ann", 12.5, true) }),
+            json!({ "action": "insert_many", "table": "players", "records": [
+                player("bob", 30.5, false), player("Cara", 7.5, true), player("Dan", 30.5, true)
+            ] }),
+            json!({ "action": "insert", "table": "players", "record": { "name": "Eve", "score": null, "active": false } }),
+            json!({ "action": "fetch", "table": "players" }),
+            json!({ "action": "fetch_by_id", "table": "players", "id": 3 }),
+            json!({ "action": "fetch_by_id", "table": "players", "id": 99 }),
+            json!({ "action": "query", "table": "players",
+                "where": [{ "column": "score", "op": ">", "value": 10 }],
+                "order_by": [{ "column": "score", "descending": true }, { "column": "name", "descending": false }] }),
+            json!({ "action": "query", "table": "players", "order_by": [{ "column": "score", "descending": false }], "limit": 2, "offset": 1 }),
+            json!({ "action": "query", "table": "players", "offset": 3 }),
+            json!({ "action": "query", "table": "players", "where": [{ "column": "name", "op": "like", "value": "B%" }] }),
+            json!({ "action": "query", "table": "players", "where": [{ "column": "active", "op": "=", "value": true }] }),
+            json!({ "action": "query", "table": "players", "where": [{ "column": "score", "op": "!=", "value": null }] }),
+            json!({ "action": "update", "table": "players", "record": { "id": 1, "score": 15.5 } }),
+            json!({ "action": "update", "table": "players", "record": { "id": 42, "score": 1.5 } }),
+            json!({ "action": "update", "table": "players", "record": { "id": 1 } }),
+            json!({ "action": "update_by_column", "table": "players", "id": 2, "column": "name", "value": "Bob" }),
+            json!({ "action": "update_by_column", "table": "players", "id": 2, "column": "id", "value": 99 }),
+            json!({ "action": "update_many", "table": "players", "records": [{ "id": 3, "active": false }, { "id": 4, "active": false }] }),
+            json!({ "action": "delete", "table": "players", "id": 5 }),
+            json!({ "action": "delete", "table": "players", "id": 5 }),
+            json!({ "action": "delete_where", "table": "players" }),
+            json!({ "action": "delete_where", "table": "players", "where": [{ "column": "score", "op": "<", "value": 10 }] }),
+            // The second operation is bad, so the first must not happen either
+            json!({ "action": "transaction", "operations": [
+                { "action": "insert", "table": "players", "record": player("Fay", 1.5, true) },
+                { "action": "delete", "table": "players" }
+            ] }),
+            json!({ "action": "transaction", "operations": [
+                { "action": "insert", "table": "players", "record": player("Gus", 2.5, true) },
+                { "action": "update_by_column", "table": "players", "id": 1, "column": "active", "value": false },
+                { "action": "delete", "table": "players", "id": 4 }
+            ] }),
+            json!({ "action": "transaction", "operations": [{ "action": "fetch", "table": "players" }] }),
+            json!({ "action": "query", "table": "players", "where": [{ "column": "name", "op": "~", "value": 1 }] }),
+            json!({ "action": "fetch", "table": "bad name" }),
+            json!({ "action": "drop", "table": "players" }),
+            json!({ "action": "fetch", "table": "players" }),
+            json!({ "action": "count", "table": "players", "where": [{ "column": "active", "op": "=", "value": true }] }),
+            json!({ "action": "count", "table": "players", "limit": 1 }),
+            json!({ "action": "create_table", "table": "entries", "unique": ["board", "player"], "columns": [
+                { "name": "board", "type": "TEXT" },
+                { "name": "player", "type": "TEXT" },
+                { "name": "score", "type": "REAL" }
+            ] }),
+            json!({ "action": "insert", "table": "entries", "record": { "board": "a", "player": "Ann", "score": 1.5 } }),
+            json!({ "action": "insert", "table": "entries", "record": { "board": "a", "player": "Ann", "score": 2.5 } }),
+            json!({ "action": "insert", "table": "entries", "record": { "board": "b", "player": "Ann", "score": 3.5 } }),
+            json!({ "action": "update", "table": "entries", "record": { "id": 2, "board": "a" } }),
+            json!({ "action": "transaction", "operations": [
+                { "action": "insert", "table": "entries", "record": { "board": "c", "player": "Bob", "score": 1.5 } },
+                { "action": "insert", "table": "entries", "record": { "board": "c", "player": "Bob", "score": 2.5 } }
+            ] }),
+            json!({ "action": "count", "table": "entries" }),
+            json!({ "action": "create_table", "table": "others", "unique": ["nope"], "columns": [{ "name": "board", "type": "TEXT" }] }),
+        ]
+    }
+
+    #[test]
+    fn memory_backend_runs_script() {
+        let backend = MemoryBackend::new();
+        let answers: Vec<Value> = script().iter().map(|request| answer(backend.handle(request))).collect();
+        assert_eq!(answers[2], json!({ "inserted": 3, "ids": [2, 3, 4] }));
+        assert_eq!(answers[15], json!({ "error": 400 }));
+        assert_eq!(answers[17], json!({ "error": 400 }));
+        assert_eq!(answers[23], json!({ "error": 400 }));
+        let names: Vec<&str> = answers[29]["records"].as_array().unwrap().iter().map(|r| r["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["Ann", "Bob", "Gus"]);
+        assert_eq!(answers[30], json!({ "count": 1 }));
+        assert_eq!(answers[31], json!({ "count": 3 }));
+        assert_eq!(answers[34], json!({ "error": 409 }));
+        assert_eq!(answers[36], json!({ "error": 409 }));
+        assert_eq!(answers[37], json!({ "error": 409 }));
+        assert_eq!(answers[38], json!({ "count": 2 }));
+        assert_eq!(answers[39], json!({ "error": 400 }));
+        // The id couldn't be changed, so the row is still found by its id
+        assert_eq!(answer(backend.handle(&json!({ "action": "fetch_by_id", "table": "players", "id": 2 })))["record"]["id"], 2);
+    }
+
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    #[test]
+    fn memory_and_sqlite_give_the_same_answers() {
+        let memory = MemoryBackend::new();
+        let sqlite = SqliteBackend::open_in_memory().unwrap();
+        for request in script() {
+            assert_eq!(answer(memory.handle(&request)), answer(sqlite.handle(&request)), "request: {}", request);
+        }
+    }
+
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    #[test]
+    fn players_only_change_their_own_rows() {
+        let sqlite = SqliteBackend::open_in_memory().unwrap();
+        sqlite.execute_sql("CREATE TABLE _players (id INTEGER PRIMARY KEY, name TEXT); CREATE TABLE _sessions (token TEXT)").unwrap();
+        sqlite
+            .handle(&json!({ "action": "create_table", "table": "notes", "columns": [
+                { "name": "owner_id", "type": "INTEGER" },
+                { "name": "text", "type": "TEXT" }
+            ] }))
+            .unwrap();
+        let ann = Caller::Player(1);
+        let bob = Caller::Player(2);
+        let note = |text: &str| json!({ "action": "insert", "table": "notes", "record": { "owner_id": 2, "text": text } });
+
+        // Guests can't write, and a player can't insert a row for someone else
+        assert_eq!(answer(sqlite.handle_as(&note("hi"), Caller::Guest)), json!({ "error": 401 }));
+        assert_eq!(answer(sqlite.handle_as(&note("hi"), ann)), json!({ "success": true, "id": 1 }));
+        let row = answer(sqlite.handle(&json!({ "action": "fetch_by_id", "table": "notes", "id": 1 })));
+        assert_eq!(row["record"]["owner_id"], 1);
+
+        // Another player's writes don't touch the row
+        let writes = [
+            json!({ "action": "update", "table": "notes", "record": { "id": 1, "text": "bob was here" } }),
+            json!({ "action": "update_by_column", "table": "notes", "id": 1, "column": "text", "value": "bob was here" }),
+            json!({ "action": "delete", "table": "notes", "id": 1 }),
+            json!({ "action": "delete_where", "table": "notes", "where": [{ "column": "text", "op": "=", "value": "hi" }] }),
+        ];
+        for request in &writes {
+            let result = answer(sqlite.handle_as(request, bob));
+            assert_eq!(result.get("updated").or(result.get("deleted")), Some(&json!(0)), "request: {}", request);
+        }
+        assert_eq!(answer(sqlite.handle(&json!({ "action": "count", "table": "notes" }))), json!({ "count": 1 }));
+
+        // The owner can't give the row away either
+        let give_away = json!({ "action": "update_by_column", "table": "notes", "id": 1, "column": "owner_id", "value": 2 });
+        assert_eq!(answer(sqlite.handle_as(&give_away, ann)), json!({ "error": 403 }));
+        assert_eq!(answer(sqlite.handle_as(&writes[0], ann)), json!({ "updated": 1 }));
+
+        // Tables starting with _ can't be used at all
+        for table in ["_players", "_sessions"] {
+            let requests = [
+                json!({ "action": "fetch", "table": table }),
+                json!({ "action": "fetch_by_id", "table": table, "id": 1 }),
+                json!({ "action": "query", "table": table }),
+                json!({ "action": "count", "table": table }),
+                json!({ "action": "create_table", "table": table, "columns": [{ "name": "name", "type": "TEXT" }] }),
+                json!({ "action": "insert", "table": table, "record": { "name": "Ann" } }),
+                json!({ "action": "insert_many", "table": table, "records": [{ "name": "Ann" }] }),
+                json!({ "action": "update", "table": table, "record": { "id": 1, "name": "Ann" } }),
+                json!({ "action": "update_many", "table": table, "records": [{ "id": 1, "name": "Ann" }] }),
+                json!({ "action": "update_by_column", "table": table, "id": 1, "column": "name", "value": "Ann" }),
+                json!({ "action": "delete", "table": table, "id": 1 }),
+                json!({ "action": "delete_where", "table": table, "where": [{ "column": "name", "op": "=", "value": "Ann" }] }),
+                json!({ "action": "transaction", "operations": [{ "action": "delete", "table": table, "id": 1 }] }),
+            ];
+            for request in &requests {
+                for caller in [Caller::Guest, ann] {
+                    assert_eq!(answer(sqlite.handle_as(request, caller)), json!({ "error": 403 }), "request: {}", request);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn like_matches_sql() {
+        assert!(like("Hello", "h%"));
+        assert!(like("Hello", "%LL%"));
+        assert!(like("Hello", "H_llo"));
+        assert!(like("", "%"));
+        assert!(!like("Hello", "H_lo"));
+        assert!(!like("Hello", "ello"));
+        assert!(!like("", "_"));
+    }
+
+    #[test]
+    fn sql_order_matches_sqlite() {
+        assert_eq!(sql_order(&Value::Null, &json!(0)), Ordering::Less);
+        assert_eq!(sql_order(&json!(5), &json!("1")), Ordering::Less);
+        assert_eq!(sql_order(&json!(true), &json!(1)), Ordering::Equal);
+        assert_eq!(sql_order(&json!(2.5), &json!(10)), Ordering::Less);
+        assert_eq!(sql_order(&json!("b"), &json!("a")), Ordering::Greater);
+    }
+}
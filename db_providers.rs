@@ -0,0 +1,788 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Backends that let DatabaseClient use Supabase, Firebase (Firestore) or any
+Postgres REST server (PostgREST, Neon's Data API) instead of the Cloudflare worker.
+They take the same requests as the worker, so fetch_table, insert_record, queries and the
+rest of DatabaseClient work the same whichever one you pick.
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod database;
+    pub mod db_providers;
+
+Then add the following with the use commands:
+    use crate::utils::db_providers::{create_supabase_client, create_firebase_client, create_postgrest_client};
+
+Supabase:
+    1. Make a project at https://supabase.com, then copy the Project URL and the anon key
+       from Project Settings > API
+    2. Make your tables in the SQL Editor. Postgres writes the id column differently to
+       SQLite, for example:
+           CREATE TABLE messages (
+               id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+               text TEXT NOT NULL,
+               score REAL,
+               active BOOLEAN
+           );
+    3. Let the game use the table (change messages to your table name):
+           ALTER TABLE messages ENABLE ROW LEVEL SECURITY;
+           CREATE POLICY anon_all ON messages FOR ALL TO anon USING (true) WITH CHECK (true);
+    4. let client = create_supabase_client("https://abcd.supabase.co", "your-anon-key");
+
+Firebase (Firestore):
+    1. Make a project at https://console.firebase.google.com and add a Firestore database
+    2. Copy the Project ID and the Web API Key from Project settings > General
+    3. In Firestore > Rules allow reading and writing while you develop:
+           match /{document=**} { allow read, write: if true; }
+    4. let client = create_firebase_client("my-project-id", "your-web-api-key");
+    Tables are Firestore collections and are made by the first insert. Ids are numbers
+    like everywhere else, the next id for each table is kept in the _counters collection.
+
+Other Postgres REST servers (PostgREST, Neon Data API, ...):
+    let client = create_postgrest_client("https://my-server.example.com");
+    // Or with the headers and token your server needs
+    let mut backend = PostgrestBackend::new("https://my-server.example.com");
+    backend.with_header("apikey", "my-key").with_bearer("my-jwt");
+    let client = DatabaseClient::from_backend(backend);
+
+Then use the client like any other:
+    let records = client.fetch_table::<DatabaseTable>("messages").await;
+
+A logged in player's token (client.with_token, for example a Supabase or Firebase user's
+access token) is sent as "Authorization: Bearer <token>".
+
+Differences from the worker:
+    - Transactions: Firestore runs them all-or-nothing, and a whole transaction fails if
+      it updates or deletes a row that doesn't exist. Postgres REST can't, so a transaction
+      with more than one operation, or update_many with more than one record, is refused
+      with status 400. Send those writes one at a time instead.
+    - create_table_if_missing works with Firestore (nothing to make) but not Postgres REST,
+      make the tables in your database's SQL editor instead. Firestore can't keep columns
+      unique, so a struct with unique(...) columns gets an error there.
+    - count_query downloads the matching ids on Postgres REST and counts them.
+    - Firestore has no like filter, and some queries (a filter on one column sorted by
+      another) need an index. The error message has a link that makes it.
+    - register and login are only on the worker and db_server.rs.
+*/
+
+use serde_json::{json, Map, Value};
+
+use crate::utils::database::{
+    bad_request, column_update, http_request, identifier, record_fields, request_action, required_id, transaction_operations,
+    update_fields, DatabaseBackend, DatabaseClient, DbError, RequestContext, RequestFuture, WRITE_ACTIONS,
+};
+
+/// A client that uses Supabase, `project_url` is like "https://abcd.supabase.co"
+#[allow(unused)]
+pub fn create_supabase_client(project_url: &str, anon_key: &str) -> DatabaseClient {
+    DatabaseClient::from_backend(PostgrestBackend::supabase(project_url, anon_key))
+}
+
+/// A client that uses a Postgres REST server at `base_url`
+#[allow(unused)]
+pub fn create_postgrest_client(base_url: &str) -> DatabaseClient {
+    DatabaseClient::from_backend(PostgrestBackend::new(base_url))
+}
+
+/// A client that uses the Firestore database of a Firebase project
+#[allow(unused)]
+pub fn create_firebase_client(project_id: &str, api_key: &str) -> DatabaseClient {
+    DatabaseClient::from_backend(FirestoreBackend::new(project_id, api_key))
+}
+
+// ============================================================================
+// SHARED HELPERS
+// ============================================================================
+
+fn fields_object(fields: &[(&str, &Value)]) -> Value {
+    Value::Object(fields.iter().map(|(key, value)| (key.to_string(), (*value).clone())).collect())
+}
+
+fn records(data: &Value) -> Result<&Vec<Value>, DbError> {
+    data["records"].as_array().ok_or_else(|| bad_request("Missing records"))
+}
+
+fn where_filters(data: &Value) -> &[Value] {
+    data["where"].as_array().map_or(&[], |filters| filters.as_slice())
+}
+
+// Percent-encodes a value for a URL
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded += &format!("%{:02X}", byte),
+        }
+    }
+    encoded
+}
+
+// ============================================================================
+// POSTGRES REST (SUPABASE, POSTGREST, NEON DATA API)
+// ============================================================================
+
+// Refused instead of sending the writes one at a time, where a failure would leave the first ones done
+const NOT_ALL_OR_NOTHING: &str = "Postgres REST can't change more than one row all-or-nothing, send the writes one at a time";
+
+/// Talks to a PostgREST server, the REST API Supabase and Neon put in front of Postgres
+pub struct PostgrestBackend {
+    base_url: String,
+    headers: Vec<(String, String)>,
+    bearer: Option<String>, // Sent when the client has no token of its own
+}
+
+#[allow(unused)]
+impl PostgrestBackend {
+    /// `base_url` is the address the tables are under, like "https://example.com/rest/v1"
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            headers: Vec::new(),
+            bearer: None,
+        }
+    }
+
+    /// Supabase's REST API, `project_url` is like "https://abcd.supabase.co"
+    pub fn supabase(project_url: &str, anon_key: &str) -> Self {
+        let mut backend = Self::new(&format!("{}/rest/v1", project_url.trim_end_matches('/')));
+        backend.with_header("apikey", anon_key).with_bearer(anon_key);
+        backend
+    }
+
+    /// A header sent with every request
+    pub fn with_header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// The bearer token sent when the client doesn't have a login token
+    pub fn with_bearer(&mut self, token: &str) -> &mut Self {
+        self.bearer = Some(token.to_string());
+        self
+    }
+
+    async fn handle(&self, data: &Value, context: &RequestContext<'_>) -> Result<Value, DbError> {
+        let action = request_action(data)?;
+        match action {
+            "fetch" => {
+                let table = identifier(&data["table"])?;
+                let rows = self.send("GET", table, &["select=*".to_string(), "order=id.asc".to_string()], None, context).await?;
+                Ok(json!({ "records": rows }))
+            }
+            "fetch_by_id" => {
+                let table = identifier(&data["table"])?;
+                let id = required_id(data, "Missing id")?;
+                let rows = self.send("GET", table, &["select=*".to_string(), format!("id=eq.{}", id)], None, context).await?;
+                Ok(json!({ "record": rows.first().cloned().unwrap_or(Value::Null) }))
+            }
+            "query" => {
+                let table = identifier(&data["table"])?;
+                let rows = self.send("GET", table, &query_params(data)?, None, context).await?;
+                Ok(json!({ "records": rows }))
+            }
+            "count" => {
+                // Only the ids come back, PostgREST puts the real count in a header we can't read
+                let table = identifier(&data["table"])?;
+                let mut params = vec!["select=id".to_string()];
+                params.extend(filter_params(data)?);
+                let rows = self.send("GET", table, &params, None, context).await?;
+                Ok(json!({ "count": rows.len() }))
+            }
+            "create_table" => Err(bad_request(
+                "Postgres REST can't make tables, make them in your database's SQL editor",
+            )),
+            "transaction" => {
+                // Each request is its own transaction, so only one operation can be all-or-nothing
+                let operations = transaction_operations(data)?;
+                if operations.len() > 1 {
+                    return Err(bad_request(NOT_ALL_OR_NOTHING));
+                }
+                Ok(json!({ "results": [self.write(&operations[0], context).await?] }))
+            }
+            _ if WRITE_ACTIONS.contains(&action) => self.write(data, context).await,
+            _ => Err(bad_request("Unknown action")),
+        }
+    }
+
+    async fn write(&self, op: &Value, context: &RequestContext<'_>) -> Result<Value, DbError> {
+        let table = identifier(&op["table"])?;
+        match op["action"].as_str().unwrap_or_default() {
+            "insert" => {
+                let record = fields_object(&record_fields(&op["record"])?);
+                let rows = self.send("POST", table, &[], Some(record), context).await?;
+                Ok(json!({ "success": !rows.is_empty(), "id": rows.first().map_or(0, |row| row["id"].as_i64().unwrap_or(0)) }))
+            }
+            "insert_many" => {
+                let records = records(op)?;
+                if records.is_empty() {
+                    return Ok(json!({ "inserted": 0, "ids": [] }));
+                }
+                let body = records
+                    .iter()
+                    .map(|record| record_fields(record).map(|fields| fields_object(&fields)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let rows = self.send("POST", table, &[], Some(Value::Array(body)), context).await?;
+                let ids: Vec<i64> = rows.iter().map(|row| row["id"].as_i64().unwrap_or(0)).collect();
+                Ok(json!({ "inserted": ids.len(), "ids": ids }))
+            }
+            "update" => {
+                if op["record"]["id"].is_null() {
+                    return Err(bad_request("Missing record or id"));
+                }
+                Ok(json!({ "updated": self.update(table, &op["record"], context).await? }))
+            }
+            "update_many" => {
+                let records = records(op)?;
+                if records.iter().any(|record| record["id"].is_null()) {
+                    return Err(bad_request("Missing id in records"));
+                }
+                // Each row is its own PATCH, so more than one couldn't be all-or-nothing
+                if records.len() > 1 {
+                    return Err(bad_request(NOT_ALL_OR_NOTHING));
+                }
+                let mut updated = 0;
+                for record in records {
+                    updated += self.update(table, record, context).await?;
+                }
+                Ok(json!({ "updated": updated }))
+            }
+            "update_by_column" => {
+                let (id, column) = column_update(op)?;
+                let body = json!({ column: op["value"] });
+                let rows = self.send("PATCH", table, &[format!("id=eq.{}", id)], Some(body), context).await?;
+                Ok(json!({ "updated": rows.len() }))
+            }
+            "delete" => {
+                let id = required_id(op, "Missing id")?;
+                let rows = self.send("DELETE", table, &[format!("id=eq.{}", id)], None, context).await?;
+                Ok(json!({ "deleted": rows.len() }))
+            }
+            "delete_where" => {
+                // Never delete a whole table by accident
+                if where_filters(op).is_empty() {
+                    return Err(bad_request("Missing where"));
+                }
+                let rows = self.send("DELETE", table, &filter_params(op)?, None, context).await?;
+                Ok(json!({ "deleted": rows.len() }))
+            }
+            _ => Err(bad_request("Unknown action")),
+        }
+    }
+
+    async fn update(&self, table: &str, record: &Value, context: &RequestContext<'_>) -> Result<usize, DbError> {
+        let id = record["id"].as_i64().ok_or_else(|| bad_request("Missing record or id"))?;
+        let body = fields_object(&update_fields(record)?);
+        let rows = self.send("PATCH", table, &[format!("id=eq.{}", id)], Some(body), context).await?;
+        Ok(rows.len())
+    }
+
+    // One request, gives back the rows PostgREST returns
+    async fn send(
+        &self,
+        method: &str,
+        table: &str,
+        params: &[String],
+        body: Option<Value>,
+        context: &RequestContext<'_>,
+    ) -> Result<Vec<Value>, DbError> {
+        let mut url = format!("{}/{}", self.base_url, table);
+        if !params.is_empty() {
+            url += &format!("?{}", params.join("&"));
+        }
+
+        let mut headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+            // Writes answer with the rows they changed, so they can be counted
+            ("Prefer".to_string(), "return=representation".to_string()),
+        ];
+        headers.extend(self.headers.iter().cloned());
+        if let Some(token) = context.token().or(self.bearer.as_deref()) {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+
+        let text = http_request(method, &url, &headers, body.map(|body| body.to_string()), context).await?;
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        match serde_json::from_str(&text)? {
+            Value::Array(rows) => Ok(rows),
+            row => Ok(vec![row]),
+        }
+    }
+}
+
+impl DatabaseBackend for PostgrestBackend {
+    fn execute<'a>(&'a self, payload: &'a Value, context: &'a RequestContext<'a>) -> RequestFuture<'a, Value> {
+        Box::pin(self.handle(payload, context))
+    }
+}
+
+// Filters, sorting and paging from the Rust Query builder as URL parameters
+fn query_params(data: &Value) -> Result<Vec<String>, DbError> {
+    let mut params = vec!["select=*".to_string()];
+    params.extend(filter_params(data)?);
+
+    let mut sorts = Vec::new();
+    for sort in data["order_by"].as_array().into_iter().flatten() {
+        let direction = if sort["descending"].as_bool().unwrap_or(false) { "desc" } else { "asc" };
+        sorts.push(format!("{}.{}", identifier(&sort["column"])?, direction));
+    }
+    if !sorts.is_empty() {
+        params.push(format!("order={}", sorts.join(",")));
+    }
+    if let Some(limit) = data["limit"].as_u64() {
+        params.push(format!("limit={}", limit));
+    }
+    if let Some(offset) = data["offset"].as_u64() {
+        params.push(format!("offset={}", offset));
+    }
+    Ok(params)
+}
+
+// "column=op.value" for each filter
+fn filter_params(data: &Value) -> Result<Vec<String>, DbError> {
+    let mut params = Vec::new();
+    for filter in where_filters(data) {
+        let column = identifier(&filter["column"])?;
+        let value = &filter["value"];
+        let text = match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        let condition = match (filter["op"].as_str().unwrap_or_default(), value.is_null()) {
+            ("=", true) => "is.null".to_string(),
+            ("!=", true) => "not.is.null".to_string(),
+            ("=", false) => format!("eq.{}", text),
+            ("!=", false) => format!("neq.{}", text),
+            ("<", _) => format!("lt.{}", text),
+            ("<=", _) => format!("lte.{}", text),
+            (">", _) => format!("gt.{}", text),
+            (">=", _) => format!("gte.{}", text),
+            ("like", _) => format!("like.{}", text),
+            _ => return Err(bad_request("Invalid operator")),
+        };
+        params.push(format!("{}={}", column, url_encode(&condition)));
+    }
+    Ok(params)
+}
+
+// ============================================================================
+// FIREBASE (FIRESTORE)
+// ============================================================================
+
+/// Talks to Cloud Firestore through its REST API
+pub struct FirestoreBackend {
+    project_id: String,
+    api_key: String,
+}
+
+// One planned write: the Firestore writes to commit and what to answer once they worked
+struct WritePlan {
+    writes: Vec<Value>,
+    result: Value,
+}
+
+#[allow(unused)]
+impl FirestoreBackend {
+    pub fn new(project_id: &str, api_key: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    async fn handle(&self, data: &Value, context: &RequestContext<'_>) -> Result<Value, DbError> {
+        let action = request_action(data)?;
+        match action {
+            "fetch" => {
+                let mut records = self.list(identifier(&data["table"])?, context).await?;
+                records.sort_by_key(|record| record["id"].as_i64().unwrap_or(0));
+                Ok(json!({ "records": records }))
+            }
+            "fetch_by_id" => {
+                let id = required_id(data, "Missing id")?;
+                let path = self.document(identifier(&data["table"])?, id);
+                match self.send("GET", &path, &[], None, context).await {
+                    Ok(document) => Ok(json!({ "record": from_document(&document) })),
+                    Err(DbError::NotFound) => Ok(json!({ "record": null })),
+                    Err(err) => Err(err),
+                }
+            }
+            "query" => Ok(json!({ "records": self.run_query(data, true, context).await? })),
+            "count" => {
+                let query = json!({
+                    "structuredQuery": structured_query(data, false)?,
+                    "aggregations": [{ "alias": "count", "count": {} }]
+                });
+                let path = format!("{}:runAggregationQuery", self.documents_path());
+                let answer = self.send("POST", &path, &[], Some(json!({ "structuredAggregationQuery": query })), context).await?;
+                let count = answer[0]["result"]["aggregateFields"]["count"]["integerValue"]
+                    .as_str()
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .ok_or_else(|| DbError::Decode("Firestore didn't send the count".to_string()))?;
+                Ok(json!({ "count": count }))
+            }
+            // Collections are made by their first document
+            "create_table" => {
+                identifier(&data["table"])?;
+                if data["unique"].as_array().is_some_and(|unique| !unique.is_empty()) {
+                    return Err(bad_request("Firestore can't make unique columns, leave out create_table_if_missing"));
+                }
+                Ok(json!({ "success": true }))
+            }
+            "transaction" => {
+                let mut writes = Vec::new();
+                let mut results = Vec::new();
+                for op in transaction_operations(data)? {
+                    let plan = self.plan(op, context).await?;
+                    writes.extend(plan.writes);
+                    results.push(plan.result);
+                }
+                self.commit(writes, context).await.map_err(missing_row)?;
+                Ok(json!({ "results": results }))
+            }
+            _ if WRITE_ACTIONS.contains(&action) => {
+                let plan = self.plan(data, context).await?;
+                match self.commit(plan.writes, context).await {
+                    Ok(()) => Ok(plan.result),
+                    // Like SQL, changing a row that isn't there changes nothing
+                    Err(DbError::NotFound) if matches!(action, "update" | "update_by_column" | "delete") => {
+                        Ok(if action == "delete" { json!({ "deleted": 0 }) } else { json!({ "updated": 0 }) })
+                    }
+                    Err(err) => Err(missing_row(err)),
+                }
+            }
+            _ => Err(bad_request("Unknown action")),
+        }
+    }
+
+    // Works out the writes for one operation (new ids are handed out here)
+    async fn plan(&self, op: &Value, context: &RequestContext<'_>) -> Result<WritePlan, DbError> {
+        let table = identifier(&op["table"])?;
+        let plan = match op["action"].as_str().unwrap_or_default() {
+            "insert" => {
+                let fields = record_fields(&op["record"])?;
+                let id = self.next_ids(table, 1, context).await?[0];
+                WritePlan {
+                    writes: vec![self.insert_write(table, id, &fields)],
+                    result: json!({ "success": true, "id": id }),
+                }
+            }
+            "insert_many" => {
+                let records = records(op)?;
+                let fields = records.iter().map(record_fields).collect::<Result<Vec<_>, _>>()?;
+                let ids = self.next_ids(table, fields.len(), context).await?;
+                WritePlan {
+                    writes: ids.iter().zip(&fields).map(|(id, fields)| self.insert_write(table, *id, fields)).collect(),
+                    result: json!({ "inserted": ids.len(), "ids": ids }),
+                }
+            }
+            "update" => {
+                let id = op["record"]["id"].as_i64().ok_or_else(|| bad_request("Missing record or id"))?;
+                WritePlan {
+                    writes: vec![self.update_write(table, id, &update_fields(&op["record"])?)],
+                    result: json!({ "updated": 1 }),
+                }
+            }
+            "update_many" => {
+                let records = records(op)?;
+                let mut writes = Vec::new();
+                for record in records {
+                    let id = record["id"].as_i64().ok_or_else(|| bad_request("Missing id in records"))?;
+                    writes.push(self.update_write(table, id, &update_fields(record)?));
+                }
+                WritePlan {
+                    result: json!({ "updated": writes.len() }),
+                    writes,
+                }
+            }
+            "update_by_column" => {
+                let (id, column) = column_update(op)?;
+                WritePlan {
+                    writes: vec![self.update_write(table, id, &[(column, &op["value"])])],
+                    result: json!({ "updated": 1 }),
+                }
+            }
+            "delete" => {
+                let id = required_id(op, "Missing id")?;
+                WritePlan {
+                    writes: vec![self.delete_write(table, id)],
+                    result: json!({ "deleted": 1 }),
+                }
+            }
+            "delete_where" => {
+                // Never delete a whole collection by accident
+                if where_filters(op).is_empty() {
+                    return Err(bad_request("Missing where"));
+                }
+                let matches = self.run_query(op, false, context).await?;
+                WritePlan {
+                    writes: matches.iter().map(|record| self.delete_write(table, record["id"].as_i64().unwrap_or(0))).collect(),
+                    result: json!({ "deleted": matches.len() }),
+                }
+            }
+            _ => return Err(bad_request("Unknown action")),
+        };
+        Ok(plan)
+    }
+
+    // Every document in a collection, a page at a time
+    async fn list(&self, table: &str, context: &RequestContext<'_>) -> Result<Vec<Value>, DbError> {
+        let path = format!("{}/{}", self.documents_path(), table);
+        let mut records = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let mut params = vec!["pageSize=300".to_string()];
+            if !page_token.is_empty() {
+                params.push(format!("pageToken={}", url_encode(&page_token)));
+            }
+            let page = self.send("GET", &path, &params, None, context).await?;
+            for document in page["documents"].as_array().into_iter().flatten() {
+                records.push(from_document(document));
+            }
+            match page["nextPageToken"].as_str() {
+                Some(token) if !token.is_empty() => page_token = token.to_string(),
+                _ => return Ok(records),
+            }
+        }
+    }
+
+    // Runs a query from the Rust Query builder (only the filters when paged is false)
+    async fn run_query(&self, data: &Value, paged: bool, context: &RequestContext<'_>) -> Result<Vec<Value>, DbError> {
+        let path = format!("{}:runQuery", self.documents_path());
+        let query = structured_query(data, paged)?;
+        let answer = self.send("POST", &path, &[], Some(json!({ "structuredQuery": query })), context).await?;
+        // One item per document, plus one without a document when nothing matched
+        Ok(answer
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| item["document"].is_object())
+            .map(|item| from_document(&item["document"]))
+            .collect())
+    }
+
+    // Runs every write together, all of them or none
+    async fn commit(&self, writes: Vec<Value>, context: &RequestContext<'_>) -> Result<(), DbError> {
+        if writes.is_empty() {
+            return Ok(());
+        }
+        let path = format!("{}:commit", self.documents_path());
+        self.send("POST", &path, &[], Some(json!({ "writes": writes })), context).await?;
+        Ok(())
+    }
+
+    // Hands out `count` new ids for a table from its counter in _counters
+    async fn next_ids(&self, table: &str, count: usize, context: &RequestContext<'_>) -> Result<Vec<i64>, DbError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let increment = json!({
+            "transform": {
+                "document": self.document_name(&format!("_counters/{}", table)),
+                "fieldTransforms": [{ "fieldPath": "next_id", "increment": { "integerValue": count.to_string() } }]
+            }
+        });
+        let path = format!("{}:commit", self.documents_path());
+        let answer = self.send("POST", &path, &[], Some(json!({ "writes": [increment] })), context).await?;
+
+        // The counter's value after adding count, so the new ids are the count below it
+        let last = answer["writeResults"][0]["transformResults"][0]["integerValue"]
+            .as_str()
+            .and_then(|value| value.parse::<i64>().ok())
+            .ok_or_else(|| DbError::Decode("Firestore didn't send the new id".to_string()))?;
+        Ok((last - count as i64 + 1..=last).collect())
+    }
+
+    fn insert_write(&self, table: &str, id: i64, fields: &[(&str, &Value)]) -> Value {
+        let mut values = Map::new();
+        values.insert("id".to_string(), to_firestore(&id.into()));
+        for (key, value) in fields {
+            values.insert(key.to_string(), to_firestore(value));
+        }
+        json!({
+            "update": { "name": self.document_name(&document_id(table, id)), "fields": values },
+            "currentDocument": { "exists": false }
+        })
+    }
+
+    fn update_write(&self, table: &str, id: i64, fields: &[(&str, &Value)]) -> Value {
+        let values: Map<String, Value> = fields.iter().map(|(key, value)| (key.to_string(), to_firestore(value))).collect();
+        json!({
+            "update": { "name": self.document_name(&document_id(table, id)), "fields": values },
+            "updateMask": { "fieldPaths": fields.iter().map(|(key, _)| *key).collect::<Vec<_>>() },
+            "currentDocument": { "exists": true }
+        })
+    }
+
+    fn delete_write(&self, table: &str, id: i64) -> Value {
+        json!({
+            "delete": self.document_name(&document_id(table, id)),
+            "currentDocument": { "exists": true }
+        })
+    }
+
+    fn documents_path(&self) -> String {
+        format!("projects/{}/databases/(default)/documents", self.project_id)
+    }
+
+    // The full name Firestore uses in writes
+    fn document_name(&self, path: &str) -> String {
+        format!("{}/{}", self.documents_path(), path)
+    }
+
+    // The URL path of one document
+    fn document(&self, table: &str, id: i64) -> String {
+        self.document_name(&document_id(table, id))
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        path: &str,
+        params: &[String],
+        body: Option<Value>,
+        context: &RequestContext<'_>,
+    ) -> Result<Value, DbError> {
+        let mut url = format!("https://firestore.googleapis.com/v1/{}?key={}", path, url_encode(&self.api_key));
+        for param in params {
+            url += &format!("&{}", param);
+        }
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(token) = context.token() {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+
+        let text = http_request(method, &url, &headers, body.map(|body| body.to_string()), context).await?;
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+impl DatabaseBackend for FirestoreBackend {
+    fn execute<'a>(&'a self, payload: &'a Value, context: &'a RequestContext<'a>) -> RequestFuture<'a, Value> {
+        Box::pin(self.handle(payload, context))
+    }
+}
+
+// Document ids are padded so Firestore's name order is the same as id order
+fn document_id(table: &str, id: i64) -> String {
+    format!("{}/{:010}", table, id)
+}
+
+// A commit that failed because a row it changes is missing
+fn missing_row(err: DbError) -> DbError {
+    match err {
+        DbError::NotFound => DbError::Http {
+            status: 404,
+            body: "A row to change doesn't exist, nothing was changed".to_string(),
+        },
+        err => err,
+    }
+}
+
+// A Firestore structured query from the Rust Query builder (only the filters when paged is false)
+fn structured_query(data: &Value, paged: bool) -> Result<Value, DbError> {
+    let mut query = json!({ "from": [{ "collectionId": identifier(&data["table"])? }] });
+
+    let mut filters = Vec::new();
+    for filter in where_filters(data) {
+        filters.push(firestore_filter(filter)?);
+    }
+    match filters.len() {
+        0 => {}
+        1 => query["where"] = filters.remove(0),
+        _ => query["where"] = json!({ "compositeFilter": { "op": "AND", "filters": filters } }),
+    }
+
+    if paged {
+        let mut order_by = Vec::new();
+        for sort in data["order_by"].as_array().into_iter().flatten() {
+            let direction = if sort["descending"].as_bool().unwrap_or(false) { "DESCENDING" } else { "ASCENDING" };
+            order_by.push(json!({ "field": { "fieldPath": identifier(&sort["column"])? }, "direction": direction }));
+        }
+        if !order_by.is_empty() {
+            query["orderBy"] = Value::Array(order_by);
+        }
+        if let Some(limit) = data["limit"].as_u64() {
+            query["limit"] = limit.into();
+        }
+        if let Some(offset) = data["offset"].as_u64() {
+            query["offset"] = offset.into();
+        }
+    }
+    Ok(query)
+}
+
+fn firestore_filter(filter: &Value) -> Result<Value, DbError> {
+    let field = json!({ "fieldPath": identifier(&filter["column"])? });
+    let value = &filter["value"];
+    let op = match (filter["op"].as_str().unwrap_or_default(), value.is_null()) {
+        ("=", true) => return Ok(json!({ "unaryFilter": { "field": field, "op": "IS_NULL" } })),
+        ("!=", true) => return Ok(json!({ "unaryFilter": { "field": field, "op": "IS_NOT_NULL" } })),
+        ("=", false) => "EQUAL",
+        ("!=", false) => "NOT_EQUAL",
+        ("<", _) => "LESS_THAN",
+        ("<=", _) => "LESS_THAN_OR_EQUAL",
+        (">", _) => "GREATER_THAN",
+        (">=", _) => "GREATER_THAN_OR_EQUAL",
+        ("like", _) => return Err(bad_request("Firestore doesn't support like filters")),
+        _ => return Err(bad_request("Invalid operator")),
+    };
+    Ok(json!({ "fieldFilter": { "field": field, "op": op, "value": to_firestore(value) } }))
+}
+
+// A Firestore document as a plain record, with its id
+fn from_document(document: &Value) -> Value {
+    let mut record = Map::new();
+    for (key, value) in document["fields"].as_object().into_iter().flatten() {
+        record.insert(key.clone(), from_firestore(value));
+    }
+    if !record.contains_key("id") {
+        let id = document["name"].as_str().and_then(|name| name.rsplit('/').next()).and_then(|id| id.parse::<i64>().ok());
+        record.insert("id".to_string(), id.into());
+    }
+    Value::Object(record)
+}
+
+// JSON to Firestore's typed values ({"integerValue": "5"}, {"stringValue": "hi"}, ...)
+fn to_firestore(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullValue": null }),
+        Value::Bool(b) => json!({ "booleanValue": b }),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => json!({ "integerValue": i.to_string() }),
+            (None, Some(u)) => json!({ "integerValue": u.to_string() }),
+            _ => json!({ "doubleValue": n.as_f64().unwrap_or(0.0) }),
+        },
+        Value::String(text) => json!({ "stringValue": text }),
+        Value::Array(items) => json!({ "arrayValue": { "values": items.iter().map(to_firestore).collect::<Vec<_>>() } }),
+        Value::Object(fields) => {
+            let fields: Map<String, Value> = fields.iter().map(|(key, value)| (key.clone(), to_firestore(value))).collect();
+            json!({ "mapValue": { "fields": fields } })
+        }
+    }
+}
+
+// Firestore's typed values back to plain JSON
+fn from_firestore(value: &Value) -> Value {
+    let Some((kind, inner)) = value.as_object().and_then(|object| object.iter().next()) else {
+        return Value::Null;
+    };
+    match kind.as_str() {
+        "integerValue" => inner.as_str().and_then(|text| text.parse::<i64>().ok()).map_or(Value::Null, Value::from),
+        "doubleValue" => inner.as_f64().map_or(Value::Null, Value::from),
+        "booleanValue" | "stringValue" | "timestampValue" | "referenceValue" | "bytesValue" | "geoPointValue" => inner.clone(),
+        "arrayValue" => Value::Array(inner["values"].as_array().into_iter().flatten().map(from_firestore).collect()),
+        "mapValue" => Value::Object(
+            inner["fields"].as_object().into_iter().flatten().map(|(key, value)| (key.clone(), from_firestore(value))).collect(),
+        ),
+        _ => Value::Null,
+    }
+}
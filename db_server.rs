@@ -0,0 +1,573 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: A local copy of the Cloudflare database worker, written in Rust and
+backed by an SQLite file. It answers the same requests as the worker in db-directions.md,
+so DatabaseClient::new("http://127.0.0.1:8787".to_string()) works with no internet and
+no Cloudflare account. Desktop only.
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod database;
+    pub mod db_local;
+    pub mod db_server;
+
+This uses SqliteBackend, so add rusqlite and the sqlite feature as shown at the top of db_local.rs.
+Passwords are hashed with PBKDF2, add it with the following commands in the terminal:
+    cargo add pbkdf2@0.12 --features hmac --target 'cfg(not(target_arch = "wasm32"))'
+    cargo add sha2@0.10 --target 'cfg(not(target_arch = "wasm32"))'
+
+Running it as its own program (in a second terminal while you work on the game):
+    1. Make a folder called bin inside src, and in it a file called db_server.rs with:
+
+        #![allow(dead_code)]
+
+        #[path = "../utils/textfiles.rs"]
+        pub mod textfiles;
+        #[path = "../utils/database.rs"]
+        pub mod database;
+        #[path = "../utils/db_local.rs"]
+        pub mod db_local;
+        #[path = "../utils/db_server.rs"]
+        pub mod db_server;
+
+        // The files expect to be inside utils, like in the game
+        mod utils {
+            pub use super::{database, db_local, db_server, textfiles};
+        }
+
+        fn main() {
+            if let Err(e) = utils::db_server::run_from_args() {
+                eprintln!("{}", e);
+            }
+        }
+
+    2. Now there are two programs, so tell cargo which one `cargo run` starts.
+       In Cargo.toml under [package] add (with your project's name):
+        default-run = "my_game"
+
+    3. Start the server:
+        cargo run --bin db_server
+        cargo run --bin db_server -- --port 8787 --db dev.db --schema schema.sql --api-key my-game-key
+
+        --port     Port to listen on (default 8787)
+        --db       SQLite file to use (default dev.db, :memory: for one that is thrown away)
+        --schema   SQL file run at the start, put your CREATE TABLE IF NOT EXISTS statements in it
+        --api-key  Only answer requests with this key (DatabaseClient::with_api_key)
+
+Then in the game:
+    let client = DatabaseClient::new("http://127.0.0.1:8787".to_string());
+
+Starting it inside your program instead (for example in tests), it runs on its own thread
+and stops when it is dropped:
+    use crate::utils::db_server::LocalServer;
+
+    let server = LocalServer::start("127.0.0.1:0", ":memory:", SCHEMA).unwrap();   // Port 0 picks a free port
+    let client = DatabaseClient::new(server.url());
+    // ... use the client ...
+    server.stop();
+
+    // Or with an API key
+    let server = LocalServer::start_with_api_key("127.0.0.1:0", ":memory:", SCHEMA, "my-game-key").unwrap();
+
+Player accounts work like the worker's: register, login and logout actions (see
+DatabaseClient::login), sessions last 30 days, and tables with an owner_id column can
+only be changed by the player who inserted each row. Accounts are kept in the _players
+and _sessions tables, which requests can't read or change.
+
+Note: This is for development. Keep it on 127.0.0.1, it doesn't use HTTPS so passwords
+and tokens are sent as plain text.
+*/
+
+#![cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+
+use rusqlite::OptionalExtension;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::utils::database::DbError;
+use crate::utils::db_local::{Caller, SqliteBackend};
+
+pub const DEFAULT_PORT: u16 = 8787;
+
+// Bigger requests than this are refused
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+// How long a login lasts
+const SESSION_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+// PBKDF2 rounds, the same as the worker uses
+const HASH_ROUNDS: u32 = 100_000;
+
+// Player accounts, made when the server starts
+const ACCOUNT_TABLES: &str = "
+    CREATE TABLE IF NOT EXISTS _players (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+        password_hash TEXT NOT NULL,
+        salt TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS _sessions (
+        token TEXT PRIMARY KEY,
+        player_id INTEGER NOT NULL,
+        expires_at INTEGER NOT NULL
+    );
+";
+
+/// The server running on its own thread, stopped when dropped
+pub struct LocalServer {
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[allow(unused)]
+impl LocalServer {
+    /// Start serving on `address` (like "127.0.0.1:8787") using the SQLite file at `db_path`
+    /// `schema` is SQL run before the first request (use "" for none)
+    pub fn start(address: &str, db_path: &str, schema: &str) -> Result<Self, String> {
+        Self::spawn(address, Server::open(db_path, schema, None)?)
+    }
+
+    /// start() that only answers requests sent with `api_key`
+    pub fn start_with_api_key(address: &str, db_path: &str, schema: &str, api_key: &str) -> Result<Self, String> {
+        Self::spawn(address, Server::open(db_path, schema, Some(api_key))?)
+    }
+
+    fn spawn(address: &str, server: Server) -> Result<Self, String> {
+        let listener = TcpListener::bind(address).map_err(|e| format!("Can't listen on {}: {}", address, e))?;
+        let address = listener.local_addr().map_err(|e| e.to_string())?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            std::thread::spawn(move || server.serve(listener, &stop))
+        };
+
+        Ok(Self {
+            address,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The URL to give DatabaseClient::new
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn stop(self) {}
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+
+        // The server thread waits for a connection, so connect once to wake it up
+        let mut wake = self.address;
+        if wake.ip().is_unspecified() {
+            wake.set_ip([127, 0, 0, 1].into());
+        }
+        let _ = TcpStream::connect_timeout(&wake, Duration::from_secs(1));
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Serve on `address` until the program is closed
+#[allow(unused)]
+pub fn run(address: &str, db_path: &str, schema: &str, api_key: Option<&str>) -> Result<(), String> {
+    let listener = TcpListener::bind(address).map_err(|e| format!("Can't listen on {}: {}", address, e))?;
+    let server = Server::open(db_path, schema, api_key)?;
+    let address = listener.local_addr().map_err(|e| e.to_string())?;
+    println!("Database server running at http://{} using {}", address, db_path);
+    server.serve(listener, &AtomicBool::new(false));
+    Ok(())
+}
+
+/// run() with the --port, --db, --schema and --api-key options from the command line
+#[allow(unused)]
+pub fn run_from_args() -> Result<(), String> {
+    let mut port = DEFAULT_PORT;
+    let mut db_path = "dev.db".to_string();
+    let mut schema = String::new();
+    let mut api_key = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--port" => port = value()?.parse().map_err(|_| "--port needs a number".to_string())?,
+            "--db" => db_path = value()?,
+            "--schema" => {
+                let path = value()?;
+                schema = std::fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+            }
+            "--api-key" => api_key = Some(value()?),
+            _ => return Err(format!("Unknown option {}, use --port, --db, --schema or --api-key", arg)),
+        }
+    }
+
+    run(&format!("127.0.0.1:{}", port), &db_path, &schema, api_key.as_deref())
+}
+
+// A request after its headers and body have been read
+struct Request {
+    method: String,
+    api_key: Option<String>,
+    token: Option<String>,
+    body: Vec<u8>,
+}
+
+struct Server {
+    backend: SqliteBackend,
+    api_key: Option<String>,
+}
+
+impl Server {
+    fn open(db_path: &str, schema: &str, api_key: Option<&str>) -> Result<Self, String> {
+        let backend = if db_path == ":memory:" {
+            SqliteBackend::open_in_memory()
+        } else {
+            SqliteBackend::open(db_path)
+        }
+        .map_err(|e| e.to_string())?;
+        backend.execute_sql(ACCOUNT_TABLES).map_err(|e| e.to_string())?;
+        backend.execute_sql(schema).map_err(|e| format!("Schema failed: {}", e))?;
+
+        Ok(Self {
+            backend,
+            api_key: api_key.map(|key| key.to_string()),
+        })
+    }
+
+    // One request at a time, SQLite only has one connection anyway
+    fn serve(&self, listener: TcpListener, stop: &AtomicBool) {
+        for stream in listener.incoming() {
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            if let Ok(stream) = stream {
+                // A broken connection only affects that request
+                let _ = self.handle_connection(stream);
+            }
+        }
+    }
+
+    fn handle_connection(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let (status, body) = match read_request(&stream)? {
+            Some(request) => self.respond(&request),
+            None => (413, "Request too large".to_string()),
+        };
+
+        let content_type = if status == 200 { "application/json" } else { "text/plain" };
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: {}\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type, Authorization, X-Api-Key\r\n\
+             Connection: close\r\n\r\n{}",
+            status,
+            reason(status),
+            content_type,
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+
+    // The same answers the worker gives
+    fn respond(&self, request: &Request) -> (u16, String) {
+        match request.method.as_str() {
+            "OPTIONS" => return (204, String::new()),
+            "POST" => {}
+            _ => return (405, "Method Not Allowed".to_string()),
+        }
+        if self.api_key.is_some() && request.api_key != self.api_key {
+            return (401, "Missing or wrong API key".to_string());
+        }
+
+        let Ok(data) = serde_json::from_slice::<Value>(&request.body) else {
+            return (400, "Invalid JSON".to_string());
+        };
+        let result = match data["action"].as_str().unwrap_or_default() {
+            "register" => self.register(&data),
+            "login" => self.login(&data),
+            "logout" => self.logout(request.token.as_deref()),
+            _ => self
+                .caller(request.token.as_deref())
+                .and_then(|caller| self.backend.handle_as(&data, caller)),
+        };
+        match result {
+            Ok(reply) => (200, reply.to_string()),
+            Err(DbError::Http { status, body }) => (status, body),
+            Err(e) => (500, e.to_string()),
+        }
+    }
+
+    // Who sent the request, a token that doesn't match a session is an error
+    // so the game can tell the player to log in again
+    fn caller(&self, token: Option<&str>) -> Result<Caller, DbError> {
+        let Some(token) = token else {
+            return Ok(Caller::Guest);
+        };
+        let player_id = self
+            .backend
+            .connection()
+            .query_row(
+                "SELECT player_id FROM _sessions WHERE token = ?1 AND expires_at > ?2",
+                rusqlite::params![token, unix_time()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sql_error)?;
+        player_id
+            .map(Caller::Player)
+            .ok_or_else(|| http_error(401, "Session expired, please log in again"))
+    }
+
+    fn register(&self, data: &Value) -> Result<Value, DbError> {
+        let (username, password) = credentials(data)?;
+        let valid_name = username.len() <= 32 && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            return Err(http_error(400, "Usernames can only use letters, numbers, _ and - (up to 32)"));
+        }
+        if password.len() < 6 {
+            return Err(http_error(400, "Passwords need at least 6 characters"));
+        }
+
+        let connection = self.backend.connection();
+        let taken = connection
+            .query_row("SELECT 1 FROM _players WHERE username = ?1", [username], |_| Ok(()))
+            .optional()
+            .map_err(sql_error)?
+            .is_some();
+        if taken {
+            return Err(http_error(409, "Username taken"));
+        }
+
+        let salt = self.random_hex()?;
+        connection
+            .execute(
+                "INSERT INTO _players (username, password_hash, salt) VALUES (?1, ?2, ?3)",
+                rusqlite::params![username, hash_password(password, &salt), salt],
+            )
+            .map_err(sql_error)?;
+        self.start_session(connection.last_insert_rowid(), username)
+    }
+
+    fn login(&self, data: &Value) -> Result<Value, DbError> {
+        let (username, password) = credentials(data)?;
+        let player: Option<(i64, String, String, String)> = self
+            .backend
+            .connection()
+            .query_row(
+                "SELECT id, username, password_hash, salt FROM _players WHERE username = ?1",
+                [username],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(sql_error)?;
+
+        // The same answer for a wrong name or a wrong password
+        match player {
+            Some((player_id, username, hash, salt)) if same_text(&hash_password(password, &salt), &hash) => {
+                self.start_session(player_id, &username)
+            }
+            _ => Err(http_error(401, "Wrong username or password")),
+        }
+    }
+
+    fn logout(&self, token: Option<&str>) -> Result<Value, DbError> {
+        if let Some(token) = token {
+            self.backend
+                .connection()
+                .execute("DELETE FROM _sessions WHERE token = ?1", [token])
+                .map_err(sql_error)?;
+        }
+        Ok(json!({ "success": true }))
+    }
+
+    // A new session token, old sessions are cleaned up at the same time
+    fn start_session(&self, player_id: i64, username: &str) -> Result<Value, DbError> {
+        let token = self.random_hex()?;
+        let connection = self.backend.connection();
+        connection
+            .execute("DELETE FROM _sessions WHERE expires_at <= ?1", [unix_time()])
+            .map_err(sql_error)?;
+        connection
+            .execute(
+                "INSERT INTO _sessions (token, player_id, expires_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![token, player_id, unix_time() + SESSION_SECONDS as i64],
+            )
+            .map_err(sql_error)?;
+        Ok(json!({ "player_id": player_id, "username": username, "token": token }))
+    }
+
+    // 32 random bytes as hex, SQLite's random numbers come from the operating system
+    fn random_hex(&self) -> Result<String, DbError> {
+        self.backend
+            .connection()
+            .query_row("SELECT lower(hex(randomblob(32)))", [], |row| row.get(0))
+            .map_err(sql_error)
+    }
+}
+
+// Reads the request line, headers and body (None if the body is too big)
+fn read_request(stream: &TcpStream) -> std::io::Result<Option<Request>> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut request = Request {
+        method: request_line.split_whitespace().next().unwrap_or_default().to_string(),
+        api_key: None,
+        token: None,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.parse().unwrap_or(0),
+            "x-api-key" => request.api_key = Some(value.to_string()),
+            "authorization" => request.token = value.strip_prefix("Bearer ").map(|token| token.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Ok(None);
+    }
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body)?;
+    Ok(Some(request))
+}
+
+fn credentials(data: &Value) -> Result<(&str, &str), DbError> {
+    match (data["username"].as_str(), data["password"].as_str()) {
+        (Some(username), Some(password)) if !username.is_empty() => Ok((username, password)),
+        _ => Err(http_error(400, "Missing username or password")),
+    }
+}
+
+// PBKDF2-SHA256 of the password, as hex
+fn hash_password(password: &str, salt: &str) -> String {
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), HASH_ROUNDS, &mut hash);
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Compares every character so the time taken doesn't give away how much matched
+fn same_text(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn unix_time() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() as i64)
+}
+
+fn http_error(status: u16, message: &str) -> DbError {
+    DbError::Http {
+        status,
+        body: message.to_string(),
+    }
+}
+
+fn sql_error(error: rusqlite::Error) -> DbError {
+    http_error(500, &format!("DB error: {}", error))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS notes (id INTEGER PRIMARY KEY AUTOINCREMENT, owner_id INTEGER, text TEXT);";
+
+    // DatabaseClient waits a frame at a time, which needs a macroquad window,
+    // so the test sends its requests with ureq and gives back the status and JSON answer
+    fn post(server: &LocalServer, api_key: Option<&str>, token: Option<&str>, body: Value) -> (u16, Value) {
+        let mut request = ureq::post(&server.url());
+        if let Some(api_key) = api_key {
+            request = request.set("X-Api-Key", api_key);
+        }
+        if let Some(token) = token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+        match request.send_string(&body.to_string()) {
+            Ok(response) => (response.status(), response.into_json().unwrap_or(Value::Null)),
+            Err(ureq::Error::Status(status, _)) => (status, Value::Null),
+            Err(e) => panic!("request failed: {}", e),
+        }
+    }
+
+    #[test]
+    fn local_server_round_trip() {
+        let server = LocalServer::start_with_api_key("127.0.0.1:0", ":memory:", SCHEMA, "game-key").unwrap();
+        let key = Some("game-key");
+        let account = json!({ "action": "register", "username": "ann", "password": "secret1" });
+
+        // Requests without the right key are refused before anything else
+        assert_eq!(post(&server, None, None, account.clone()).0, 401);
+        assert_eq!(post(&server, Some("wrong"), None, account.clone()).0, 401);
+
+        let (status, registered) = post(&server, key, None, account);
+        assert_eq!(status, 200);
+        let (status, session) = post(&server, key, None, json!({ "action": "login", "username": "ann", "password": "secret1" }));
+        assert_eq!(status, 200);
+        assert_eq!(session["player_id"], registered["player_id"]);
+        let token = session["token"].as_str();
+
+        // Guests can't write to a table with owner_id, a logged in player can
+        let insert = json!({ "action": "insert", "table": "notes", "record": { "text": "hi" } });
+        assert_eq!(post(&server, key, None, insert.clone()).0, 401);
+        assert_eq!(post(&server, key, token, insert), (200, json!({ "success": true, "id": 1 })));
+        let (_, answer) = post(&server, key, None, json!({ "action": "fetch_by_id", "table": "notes", "id": 1 }));
+        assert_eq!(answer["record"]["owner_id"], session["player_id"]);
+        assert_eq!(answer["record"]["text"], "hi");
+
+        // Dropping the server stops its thread, so nothing listens on the port anymore
+        let address = server.address();
+        drop(server);
+        assert!(TcpStream::connect_timeout(&address, Duration::from_secs(1)).is_err());
+    }
+}
@@ -0,0 +1,389 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Watches a database table for changes (chat messages, lobby players, ...).
+It asks the database every few seconds for only the rows that are new or changed since
+last time, and hands them to the game as events each frame without freezing it.
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod database;
+    pub mod db_subscription;
+
+Then add the following with the use commands:
+    use crate::utils::db_subscription::{Change, Subscription};
+
+Then above the loop you would use:
+    let client = create_database_client();
+    let mut chat = Subscription::<Message>::new(&client, "messages");
+
+    // Optional settings
+    chat.with_interval(2.0);                 // Seconds between checks (default 3)
+    chat.with_cursor_column("updated_at");   // See "Changed rows" below (default id)
+    chat.with_full_check_interval(60.0);     // Seconds between checks for deleted rows (default 30, 0 for never)
+    chat.with_existing(false);               // Don't send events for rows that were there before
+    let mut query = Query::new();
+    query.where_eq("room", 5);
+    chat.with_query(&query);                 // Only watch some of the rows
+
+Then inside the loop:
+    for change in chat.update() {
+        match change {
+            Change::Added(message) => messages_list.add_item(&message.text),
+            Change::Changed(message) => println!("Edited: {}", message.text),
+            Change::Removed(id) => println!("Message {} was deleted", id),
+        }
+    }
+
+    // Or just read the rows it is keeping up to date
+    for message in chat.rows() {
+        draw_text(&message.text, 20.0, y, 24.0, BLACK);
+    }
+
+Changed rows:
+    Checking by id only finds new rows. To also see rows that change, give the table a
+    column that goes up every time a row is written (like the time in seconds) and set it
+    whenever you insert or update:
+        message.updated_at = miniquad::date::now() as i64;
+        client.update_record_by_struct("messages", &message).await;
+    then use with_cursor_column("updated_at").
+
+Deleted rows:
+    Deleted rows leave nothing behind to ask for, so every full check interval the whole
+    table (or the rows matching with_query) is downloaded and compared instead. Keep the
+    interval long for big tables.
+
+Other options:
+    chat.refresh();                          // Check now instead of waiting
+    chat.pause(); chat.resume();             // Stop and start checking
+    chat.is_loading();                       // A check is running
+    chat.last_error();                       // The last error, checks keep going after errors
+    chat.get(id);                            // One row by id
+*/
+
+use macroquad::miniquad::date;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::utils::database::{DatabaseClient, DbError, PendingRequest, Query};
+
+/// Something that happened to a row since the last check
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<T> {
+    Added(T),
+    Changed(T),
+    Removed(i64), // The id of the row that was deleted
+}
+
+// A row as it came from the database, and as the game's type
+struct Row<T> {
+    json: Value,
+    record: T,
+}
+
+/// Keeps a copy of a table (or part of one) up to date by checking for changes
+pub struct Subscription<T> {
+    client: DatabaseClient,
+    table: String,
+    query: Query,
+    cursor_column: String,
+    interval: f64,
+    full_check_interval: f64,
+    send_existing: bool,
+    paused: bool,
+
+    rows: BTreeMap<i64, Row<T>>,
+    cursor: Option<Value>,      // The highest cursor column value seen so far
+    loaded: bool,               // The first full download has finished
+    last_check: f64,
+    last_full_check: f64,
+    pending: Option<(PendingRequest<'static, Vec<Value>>, bool)>, // The request and whether it is a full check
+    last_error: Option<DbError>,
+}
+
+#[allow(unused)]
+impl<T: DeserializeOwned + Clone> Subscription<T> {
+    pub fn new(client: &DatabaseClient, table: &str) -> Self {
+        Self {
+            client: client.clone(),
+            table: table.to_string(),
+            query: Query::new(),
+            cursor_column: "id".to_string(),
+            interval: 3.0,
+            full_check_interval: 30.0,
+            send_existing: true,
+            paused: false,
+            rows: BTreeMap::new(),
+            cursor: None,
+            loaded: false,
+            last_check: f64::MIN,
+            last_full_check: f64::MIN,
+            pending: None,
+            last_error: None,
+        }
+    }
+
+    /// Seconds between checks for new rows
+    pub fn with_interval(&mut self, seconds: f32) -> &mut Self {
+        self.interval = seconds.max(0.0) as f64;
+        self
+    }
+
+    /// A column that goes up every time a row is written, so changed rows are found too
+    pub fn with_cursor_column(&mut self, column: &str) -> &mut Self {
+        self.cursor_column = column.to_string();
+        self
+    }
+
+    /// Seconds between downloads of every row to find deleted rows (0 turns them off)
+    pub fn with_full_check_interval(&mut self, seconds: f32) -> &mut Self {
+        self.full_check_interval = seconds.max(0.0) as f64;
+        self
+    }
+
+    /// Whether rows that are already there at the start are sent as Change::Added
+    pub fn with_existing(&mut self, send: bool) -> &mut Self {
+        self.send_existing = send;
+        self
+    }
+
+    /// Only watch rows matching the query's filters (its sorting and paging are not used)
+    pub fn with_query(&mut self, query: &Query) -> &mut Self {
+        self.query.filters = query.filters.clone();
+        self
+    }
+
+    /// Call every frame, gives back what changed since the last call
+    pub fn update(&mut self) -> Vec<Change<T>> {
+        let now = date::now();
+        if self.pending.is_none() && !self.paused && now - self.last_check >= self.interval {
+            self.start_check(now);
+        }
+
+        let Some((request, full)) = &mut self.pending else {
+            return Vec::new();
+        };
+        let full = *full;
+        let Some(result) = request.poll() else {
+            return Vec::new();
+        };
+        self.pending = None;
+
+        match result {
+            Ok(records) => {
+                self.last_error = None;
+                self.apply(records, full)
+            }
+            Err(err) => {
+                self.last_error = Some(err);
+                Vec::new()
+            }
+        }
+    }
+
+    /// The rows being kept up to date, in id order
+    pub fn rows(&self) -> impl Iterator<Item = &T> {
+        self.rows.values().map(|row| &row.record)
+    }
+
+    /// One row by id
+    pub fn get(&self, id: i64) -> Option<&T> {
+        self.rows.get(&id).map(|row| &row.record)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Check on the next update() instead of waiting for the interval
+    pub fn refresh(&mut self) {
+        self.last_check = f64::MIN;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// True while a check is waiting for the database
+    pub fn is_loading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn last_error(&self) -> Option<&DbError> {
+        self.last_error.as_ref()
+    }
+
+    // Starts either a full download or a request for rows past the cursor
+    fn start_check(&mut self, now: f64) {
+        let full = !self.loaded
+            || self.cursor.is_none()
+            || (self.full_check_interval > 0.0 && now - self.last_full_check >= self.full_check_interval);
+
+        let mut query = self.query.clone();
+        if !full {
+            let cursor = self.cursor.clone().unwrap_or_default();
+            // Ids never repeat, other columns (like times) can, so rows equal to the cursor
+            // are asked for again and the ones already seen are skipped in apply()
+            if self.cursor_column == "id" {
+                query.where_gt(&self.cursor_column, cursor);
+            } else {
+                query.where_ge(&self.cursor_column, cursor);
+            }
+            query.order_by(&self.cursor_column);
+        }
+
+        let client = self.client.clone();
+        let table = self.table.clone();
+        let request = PendingRequest::new(async move { client.fetch_query::<Value>(&table, &query).await });
+        self.pending = Some((request, full));
+        self.last_check = now;
+        if full {
+            self.last_full_check = now;
+        }
+    }
+
+    // Compares the rows that came back with the ones already kept
+    fn apply(&mut self, records: Vec<Value>, full: bool) -> Vec<Change<T>> {
+        let first_load = !self.loaded;
+        let mut changes = Vec::new();
+        let mut seen = Vec::new();
+
+        for json in records {
+            let Some(id) = json["id"].as_i64() else {
+                self.last_error = Some(DbError::Decode("Row without an id".to_string()));
+                continue;
+            };
+            seen.push(id);
+            self.move_cursor(&json[self.cursor_column.as_str()]);
+
+            if self.rows.get(&id).is_some_and(|row| row.json == json) {
+                continue;
+            }
+            let record: T = match serde_json::from_value(json.clone()) {
+                Ok(record) => record,
+                Err(err) => {
+                    self.last_error = Some(err.into());
+                    continue;
+                }
+            };
+            let change = record.clone();
+            let is_new = self.rows.insert(id, Row { json, record }).is_none();
+            if first_load && !self.send_existing {
+                continue;
+            }
+            changes.push(if is_new { Change::Added(change) } else { Change::Changed(change) });
+        }
+
+        // Anything kept that a full download didn't have was deleted
+        if full {
+            seen.sort_unstable();
+            let removed: Vec<i64> = self.rows.keys().filter(|id| seen.binary_search(id).is_err()).copied().collect();
+            for id in removed {
+                self.rows.remove(&id);
+                changes.push(Change::Removed(id));
+            }
+        }
+
+        self.loaded = true;
+        changes
+    }
+
+    // Keeps the highest value of the cursor column
+    fn move_cursor(&mut self, value: &Value) {
+        if value.is_null() {
+            return;
+        }
+        let higher = match (&self.cursor, value) {
+            (None, _) => true,
+            (Some(Value::Number(a)), Value::Number(b)) => b.as_f64() > a.as_f64(),
+            (Some(Value::String(a)), Value::String(b)) => b > a,
+            _ => false,
+        };
+        if higher {
+            self.cursor = Some(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::db_local::create_memory_client;
+    use crate::utils::textfiles::test_helpers::block_on;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Message {
+        id: i64,
+        text: String,
+        updated_at: i64,
+    }
+
+    fn row(id: i64, text: &str, updated_at: i64) -> Value {
+        json!({ "id": id, "text": text, "updated_at": updated_at })
+    }
+
+    fn message(id: i64, text: &str, updated_at: i64) -> Message {
+        serde_json::from_value(row(id, text, updated_at)).unwrap()
+    }
+
+    #[test]
+    fn apply_finds_added_changed_and_removed_rows() {
+        let mut chat = Subscription::<Message>::new(&create_memory_client(), "messages");
+        let changes = chat.apply(vec![row(1, "hi", 10), row(2, "yo", 10)], true);
+        assert_eq!(changes, [Change::Added(message(1, "hi", 10)), Change::Added(message(2, "yo", 10))]);
+
+        // Rows that didn't change aren't sent again
+        let changes = chat.apply(vec![row(1, "hi", 10), row(2, "hey", 11)], false);
+        assert_eq!(changes, [Change::Changed(message(2, "hey", 11))]);
+
+        // Only a full download can tell a row was deleted
+        assert_eq!(chat.apply(vec![row(2, "hey", 11)], false), []);
+        assert_eq!(chat.apply(vec![row(2, "hey", 11)], true), [Change::Removed(1)]);
+        assert_eq!(chat.rows().cloned().collect::<Vec<Message>>(), [message(2, "hey", 11)]);
+    }
+
+    #[test]
+    fn with_existing_false_skips_the_first_load() {
+        let mut chat = Subscription::<Message>::new(&create_memory_client(), "messages");
+        chat.with_existing(false);
+        assert_eq!(chat.apply(vec![row(1, "hi", 10), row(2, "yo", 10)], true), []);
+        assert_eq!(chat.len(), 2);
+        assert_eq!(chat.apply(vec![row(3, "new", 12)], false), [Change::Added(message(3, "new", 12))]);
+    }
+
+    #[test]
+    fn rows_tied_with_the_cursor_are_found_once() {
+        let client = create_memory_client();
+        let insert = |text: &str, updated_at: i64| {
+            block_on(client.insert_record("messages", &json!({ "text": text, "updated_at": updated_at }))).unwrap()
+        };
+        insert("hi", 10);
+        insert("yo", 10);
+
+        let mut chat = Subscription::<Message>::new(&client, "messages");
+        chat.with_cursor_column("updated_at").with_interval(0.0).with_full_check_interval(0.0);
+        assert_eq!(chat.update().len(), 2);
+
+        // Written in the same second as the cursor, so only asking for later times would miss it
+        insert("same time", 10);
+        assert_eq!(chat.update(), [Change::Added(message(3, "same time", 10))]);
+        assert_eq!(chat.update(), []);
+
+        block_on(client.update_record_by_struct("messages", &row(1, "edited", 11))).unwrap();
+        assert_eq!(chat.update(), [Change::Changed(message(1, "edited", 11))]);
+        assert_eq!(chat.update(), []);
+        assert_eq!(chat.len(), 3);
+    }
+}
@@ -1,5 +1,5 @@
 // LocalStorage plugin for miniquad/wasm interop
-// Exposes js_local_storage_set and js_local_storage_get for Rust FFI
+// Exposes js_local_storage_set, js_local_storage_get and js_local_storage_remove for Rust FFI
 let local_storage_result_buffer = "";
 
 // Global paste buffer for clipboard
@@ -19,17 +19,26 @@ function js_local_storage_get(key_ptr, key_len, out_ptr, out_len) {
     const encoder = new TextEncoder();
     const key = decoder.decode(new Uint8Array(mem, key_ptr, key_len));
     const value = localStorage.getItem(key);
-    if (typeof value !== 'string') return 0;
+    if (typeof value !== 'string') return -1;
     const bytes = encoder.encode(value);
     const len = Math.min(bytes.length, out_len);
     new Uint8Array(mem, out_ptr, len).set(bytes.subarray(0, len));
-    return len;
+    // Return the full length so Rust can retry with a bigger buffer if it didn't fit
+    return bytes.length;
+}
+
+function js_local_storage_remove(key_ptr, key_len) {
+    const mem = wasm_memory.buffer;
+    const decoder = new TextDecoder();
+    const key = decoder.decode(new Uint8Array(mem, key_ptr, key_len));
+    localStorage.removeItem(key);
 }
 
 function local_storage_register_plugin(importObject) {
     if (!importObject.env) importObject.env = {};
     importObject.env.js_local_storage_set = js_local_storage_set;
     importObject.env.js_local_storage_get = js_local_storage_get;
+    importObject.env.js_local_storage_remove = js_local_storage_remove;
 }
 
 window.local_storage_register_plugin = local_storage_register_plugin;
@@ -94,52 +103,71 @@ miniquad_add_plugin({
 });
 
 // Database plugin for miniquad/wasm interop
-// Exposes mq_db_query for Rust to call via FFI
-
-let db_query_result_buffer = "";
-
-async function mq_db_query(ptr, len, url_ptr, url_len) {
-    // WASM memory is expected to be available as wasm_memory
-    try {
-        const mem = wasm_memory.buffer;
-        const decoder = new TextDecoder();
-        const body = decoder.decode(new Uint8Array(mem, ptr, len));
-        const url = decoder.decode(new Uint8Array(mem, url_ptr, url_len));
-
-        const resp = await fetch(url, {
-            method: "POST",
-            headers: {
-                "Content-Type": "application/json"
-            },
-            body
-        });
-        db_query_result_buffer = await resp.text();
-    } catch (e) {
-        db_query_result_buffer = JSON.stringify({ error: "fetch_failed", message: e && e.message ? e.message : String(e) });
-    }
+// Exposes mq_db_request_start/len/take/cancel for Rust to call via FFI
+
+// Each request gets an id so several can be running at once.
+// db_requests[id] is null while waiting and the answer's bytes once it has arrived.
+const db_requests = {};
+let db_next_request_id = 1;
+
+// headers is a JSON object of the headers to send (Content-Type, API key, login token)
+// An empty body is sent as no body, so GET and DELETE requests work too
+function mq_db_request_start(method_ptr, method_len, ptr, len, url_ptr, url_len, headers_ptr, headers_len) {
+    const mem = wasm_memory.buffer;
+    const decoder = new TextDecoder();
+    const method = decoder.decode(new Uint8Array(mem, method_ptr, method_len));
+    const body = decoder.decode(new Uint8Array(mem, ptr, len));
+    const url = decoder.decode(new Uint8Array(mem, url_ptr, url_len));
+    const headers = JSON.parse(decoder.decode(new Uint8Array(mem, headers_ptr, headers_len)));
+    const id = db_next_request_id++;
+    db_requests[id] = null;
+
+    (async () => {
+        let result;
+        try {
+            const resp = await fetch(url, {
+                method,
+                headers,
+                body: body.length > 0 ? body : undefined
+            });
+            // Pass the status along so Rust can tell HTTP errors apart from good answers
+            result = JSON.stringify({ status: resp.status, body: await resp.text() });
+        } catch (e) {
+            result = JSON.stringify({ error: "fetch_failed", message: e && e.message ? e.message : String(e) });
+        }
+        // Skip it if Rust gave up on the request while it was running
+        if (id in db_requests) {
+            db_requests[id] = new TextEncoder().encode(result);
+        }
+    })();
+
+    return id;
 }
 
-function mq_db_query_result_len() {
-    return db_query_result_buffer.length;
+// Length in bytes of the answer, or -1 while still waiting
+function mq_db_request_len(id) {
+    const result = db_requests[id];
+    return result ? result.length : -1;
 }
 
-function mq_db_query_fill_result(ptr) {
-    if (!db_query_result_buffer) return;
-    const enc = new TextEncoder();
-    const bytes = enc.encode(db_query_result_buffer);
-    new Uint8Array(wasm_memory.buffer, ptr, bytes.length).set(bytes);
+function mq_db_request_take(id, ptr) {
+    const result = db_requests[id];
+    if (result) {
+        new Uint8Array(wasm_memory.buffer, ptr, result.length).set(result);
+    }
+    delete db_requests[id];
 }
 
-function mq_db_query_clear_result() {
-    db_query_result_buffer = "";
+function mq_db_request_cancel(id) {
+    delete db_requests[id];
 }
 
 function db_register_plugin(importObject) {
     if (!importObject.env) importObject.env = {};
-    importObject.env.mq_db_query = mq_db_query;
-    importObject.env.mq_db_query_result_len = mq_db_query_result_len;
-    importObject.env.mq_db_query_fill_result = mq_db_query_fill_result;
-    importObject.env.mq_db_query_clear_result = mq_db_query_clear_result;
+    importObject.env.mq_db_request_start = mq_db_request_start;
+    importObject.env.mq_db_request_len = mq_db_request_len;
+    importObject.env.mq_db_request_take = mq_db_request_take;
+    importObject.env.mq_db_request_cancel = mq_db_request_cancel;
 }
 
 window.db_register_plugin = window.db_register_plugin;
@@ -0,0 +1,256 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Adds callbacks and an event queue for UI objects, so you don't have
+to check every object by hand each frame
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod events;
+TextButton, ImageButton, Slider, ListView and TextInput use this module, so it must be
+in ui.rs whenever one of them is.
+
+Then add the following with the use commands (only needed for the event queue):
+    use crate::ui::events::{EventQueue, UiEvent};
+
+There are two ways to react to what the user does. Use whichever is easier.
+
+1. Callbacks - give an object a closure to run when something happens:
+    btn_play.on_click(|| println!("Play pressed"));
+    btn_play.on_hover_enter(|| println!("Mouse over play"));
+    btn_play.on_hover_leave(|| println!("Mouse left play"));
+    volume_slider.on_change(|value| println!("Volume is now {}", value));
+    txt_name.on_change(|text| println!("Name is now {}", text));
+    txt_name.on_submit(|text| println!("Enter pressed with {}", text));
+    list_levels.on_select(|index, item| println!("Picked {} ({})", item, index));
+    txt_name.on_focus(|focused| println!("Name box focused: {}", focused));
+
+   Callbacks can't change your variables directly. If you need that, use the event queue.
+
+2. Event queue - objects add events to a shared queue that you go through each frame:
+    Above the loop:
+        let events = EventQueue::new();
+        btn_play.with_event_queue(&events, "play");
+        btn_quit.with_event_queue(&events, "quit");
+        volume_slider.with_event_queue(&events, "volume");
+
+    Inside the loop, after the objects have been drawn/updated:
+        for event in events.drain() {
+            match (event.id.as_str(), event.event) {
+                ("play", UiEvent::Click) => state = GameState::Playing,
+                ("quit", UiEvent::Click) => break,
+                ("volume", UiEvent::ValueChanged(value)) => volume = value,
+                _ => {}
+            }
+        }
+
+Events that each object can send:
+    TextButton / ImageButton: Click, HoverEnter, HoverLeave, Focus
+    Slider:                   ValueChanged, HoverEnter, HoverLeave, Focus
+    ListView:                 Select, HoverEnter, HoverLeave, Focus
+    TextInput:                TextChanged, Submit, HoverEnter, HoverLeave, Focus
+
+Note: Events are only sent for things the user did. Calling set_value(), set_text() or
+select_item() from code does not send an event.
+*/
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+// Something that happened to an object
+#[derive(Debug, Clone, PartialEq)]
+#[allow(unused)]
+pub enum UiEvent {
+    Click,                // A button was clicked (or pressed from the keyboard)
+    HoverEnter,           // The mouse moved onto the object
+    HoverLeave,           // The mouse moved off the object
+    ValueChanged(f32),    // A slider was moved
+    TextChanged(String),  // The text in a text input changed
+    Submit(String),       // Enter was pressed in a (single line) text input
+    Select(usize),        // An item in a list was selected
+    Focus(bool),          // The object gained (true) or lost (false) keyboard focus
+}
+
+// An event together with the id of the object that sent it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetEvent {
+    pub id: String,
+    pub event: UiEvent,
+}
+
+// A queue of events that can be shared by many objects
+// Cloning the queue gives another handle to the same events
+#[derive(Clone, Default)]
+pub struct EventQueue {
+    events: Rc<RefCell<Vec<WidgetEvent>>>,
+}
+
+#[allow(unused)]
+impl EventQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add an event to the queue
+    pub fn push(&self, id: &str, event: UiEvent) {
+        self.events.borrow_mut().push(WidgetEvent {
+            id: id.to_string(),
+            event,
+        });
+    }
+
+    // Take all events out of the queue, oldest first
+    pub fn drain(&self) -> Vec<WidgetEvent> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.borrow().len()
+    }
+
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+}
+
+// Shorter names for the callbacks that get arguments
+type TextCallback = Box<dyn Fn(&str)>;
+type SelectCallback = Box<dyn Fn(usize, &str)>;
+
+// Callbacks and queue for one object
+// Each object keeps one of these and sends its events through it
+#[derive(Default)]
+pub struct EventHandlers {
+    id: String,
+    queue: Option<EventQueue>,
+    on_click: Option<Box<dyn Fn()>>,
+    on_hover_enter: Option<Box<dyn Fn()>>,
+    on_hover_leave: Option<Box<dyn Fn()>>,
+    on_value_change: Option<Box<dyn Fn(f32)>>,
+    on_text_change: Option<TextCallback>,
+    on_submit: Option<TextCallback>,
+    on_select: Option<SelectCallback>,
+    on_focus: Option<Box<dyn Fn(bool)>>,
+    hovered: Cell<bool>, // Cell so objects that draw with &self can still track hover
+    focused: Cell<bool>,
+}
+
+#[allow(unused)]
+impl EventHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Send events to a queue, tagged with the given id
+    pub fn set_queue(&mut self, queue: &EventQueue, id: &str) {
+        self.queue = Some(queue.clone());
+        self.id = id.to_string();
+    }
+
+    pub fn set_on_click<F: Fn() + 'static>(&mut self, callback: F) {
+        self.on_click = Some(Box::new(callback));
+    }
+
+    pub fn set_on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) {
+        self.on_hover_enter = Some(Box::new(callback));
+    }
+
+    pub fn set_on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) {
+        self.on_hover_leave = Some(Box::new(callback));
+    }
+
+    pub fn set_on_value_change<F: Fn(f32) + 'static>(&mut self, callback: F) {
+        self.on_value_change = Some(Box::new(callback));
+    }
+
+    pub fn set_on_text_change<F: Fn(&str) + 'static>(&mut self, callback: F) {
+        self.on_text_change = Some(Box::new(callback));
+    }
+
+    pub fn set_on_submit<F: Fn(&str) + 'static>(&mut self, callback: F) {
+        self.on_submit = Some(Box::new(callback));
+    }
+
+    pub fn set_on_select<F: Fn(usize, &str) + 'static>(&mut self, callback: F) {
+        self.on_select = Some(Box::new(callback));
+    }
+
+    pub fn set_on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) {
+        self.on_focus = Some(Box::new(callback));
+    }
+
+    pub fn click(&self) {
+        if let Some(callback) = &self.on_click {
+            callback();
+        }
+        self.push(UiEvent::Click);
+    }
+
+    // Call every frame with whether the mouse is over the object,
+    // sends HoverEnter/HoverLeave when that changes
+    pub fn hover(&self, hovered: bool) {
+        if self.hovered.replace(hovered) == hovered {
+            return;
+        }
+
+        let callback = if hovered { &self.on_hover_enter } else { &self.on_hover_leave };
+        if let Some(callback) = callback {
+            callback();
+        }
+        self.push(if hovered { UiEvent::HoverEnter } else { UiEvent::HoverLeave });
+    }
+
+    pub fn is_hovered(&self) -> bool {
+        self.hovered.get()
+    }
+
+    pub fn value_changed(&self, value: f32) {
+        if let Some(callback) = &self.on_value_change {
+            callback(value);
+        }
+        self.push(UiEvent::ValueChanged(value));
+    }
+
+    pub fn text_changed(&self, text: &str) {
+        if let Some(callback) = &self.on_text_change {
+            callback(text);
+        }
+        self.push(UiEvent::TextChanged(text.to_string()));
+    }
+
+    pub fn submit(&self, text: &str) {
+        if let Some(callback) = &self.on_submit {
+            callback(text);
+        }
+        self.push(UiEvent::Submit(text.to_string()));
+    }
+
+    pub fn select(&self, index: usize, item: &str) {
+        if let Some(callback) = &self.on_select {
+            callback(index, item);
+        }
+        self.push(UiEvent::Select(index));
+    }
+
+    // Sends Focus only when the focus actually changes
+    pub fn focus(&self, focused: bool) {
+        if self.focused.replace(focused) == focused {
+            return;
+        }
+
+        if let Some(callback) = &self.on_focus {
+            callback(focused);
+        }
+        self.push(UiEvent::Focus(focused));
+    }
+
+    fn push(&self, event: UiEvent) {
+        if let Some(queue) = &self.queue {
+            queue.push(&self.id, event);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Adds a keyboard focus manager so objects can be used without the mouse
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod widget;
+    pub mod focus;
+
+Then add the following with the use commands:
+    use crate::ui::focus::FocusManager;
+
+Only one object has focus at a time. The focus manager:
+    - Moves focus with Tab (forward) and Shift+Tab (backward)
+    - Presses the focused TextButton or ImageButton with Enter or Space
+    - Moves a focused Slider with the arrow keys
+    - Moves the selection of a focused ListView with Up and Down
+    - Makes a focused TextInput the only one that takes typing
+    - Moves focus to whatever focusable object is clicked with the mouse
+    - Draws a focus ring around the focused object
+
+Then above the loop you would use:
+    let mut focus = FocusManager::new();
+    focus.with_ring(YELLOW, 3.0, 4.0);  // Optional: color, thickness, gap around the object
+
+Then inside the loop, BEFORE drawing the objects, pass them in the order Tab
+should visit them (objects that can't take focus, like Labels, are skipped):
+    focus.update(&mut [&mut txt_name, &mut txt_password, &mut btn_login, &mut volume_slider]);
+
+Then draw the objects as usual, and draw the focus ring last so it is on top:
+    txt_name.draw();
+    txt_password.draw();
+    if btn_login.click() {
+        // Runs for a mouse click OR for Enter/Space while the button has focus
+    }
+    volume_slider.update();
+    volume_slider.draw();
+    focus.draw();
+
+Other options:
+    focus.set_focus(Some(0));           // Focus the first object (applied on the next update)
+    focus.set_focus(None);              // Clear focus
+    let index = focus.focused();        // Index of the focused object in the list, if any
+    focus.set_enabled(false);           // Turn off keyboard handling, e.g. while a MessageBox is open
+    focus.with_ring_on_click(true);     // Also show the ring when focus came from the mouse
+
+Note: The list has to be in the same order every frame, since focus is remembered by position.
+*/
+
+use macroquad::prelude::*;
+
+use crate::ui::widget::{InputEvent, Widget};
+
+#[cfg(feature = "scale")]
+use crate::utils::scale::mouse_position_world as mouse_position;
+
+pub struct FocusManager {
+    focused: Option<usize>,
+    requested: Option<Option<usize>>, // Set by set_focus(), applied on the next update
+    enabled: bool,
+    focus_rect: Option<Rect>,
+    from_keyboard: bool,              // Was focus last moved with the keyboard
+    ring_on_click: bool,
+    ring_color: Color,
+    ring_thickness: f32,
+    ring_padding: f32,
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(unused)]
+impl FocusManager {
+    pub fn new() -> Self {
+        Self {
+            focused: None,
+            requested: None,
+            enabled: true,
+            focus_rect: None,
+            from_keyboard: false,
+            ring_on_click: false,
+            ring_color: YELLOW,
+            ring_thickness: 2.0,
+            ring_padding: 3.0,
+        }
+    }
+
+    // Set the look of the focus ring
+    pub fn with_ring(&mut self, color: Color, thickness: f32, padding: f32) -> &mut Self {
+        self.ring_color = color;
+        self.ring_thickness = thickness;
+        self.ring_padding = padding;
+        self
+    }
+
+    // Show the focus ring when an object is focused by clicking it (off by default)
+    pub fn with_ring_on_click(&mut self, show: bool) -> &mut Self {
+        self.ring_on_click = show;
+        self
+    }
+
+    // Index of the focused object in the list passed to update()
+    pub fn focused(&self) -> Option<usize> {
+        self.focused
+    }
+
+    // Move focus to an object by its index (None clears focus)
+    pub fn set_focus(&mut self, index: Option<usize>) -> &mut Self {
+        self.requested = Some(index);
+        self.from_keyboard = true;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // Turn keyboard handling on or off (the focused object keeps its focus)
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
+    // Handle Tab, Enter, Space, arrow keys and mouse clicks for the objects in the list
+    pub fn update(&mut self, widgets: &mut [&mut dyn Widget]) {
+        // Drop focus if the focused object went away, was hidden or was disabled
+        if self.focused.is_some_and(|index| index >= widgets.len() || !can_focus(&*widgets[index])) {
+            self.change_focus(widgets, None);
+        }
+
+        if let Some(request) = self.requested.take() {
+            let request = request.filter(|&index| index < widgets.len() && can_focus(&*widgets[index]));
+            self.change_focus(widgets, request);
+        }
+
+        if self.enabled {
+            // Clicking moves focus to the clicked object (or clears it)
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mouse_x, mouse_y) = mouse_position();
+                let point = Vec2::new(mouse_x, mouse_y);
+                let clicked = widgets
+                    .iter()
+                    .rposition(|widget| can_focus(&**widget) && widget.contains(point));
+                if clicked != self.focused {
+                    self.from_keyboard = false;
+                    self.change_focus(widgets, clicked);
+                }
+            }
+
+            if is_key_pressed(KeyCode::Tab) {
+                let backward = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                let next = self.next_focusable(widgets, backward);
+                self.from_keyboard = true;
+                self.change_focus(widgets, next);
+            }
+
+            if let Some(index) = self.focused {
+                let widget = &mut widgets[index];
+
+                if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) || is_key_pressed(KeyCode::Space) {
+                    widget.handle_event(&InputEvent::Activate);
+                }
+
+                for key in [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right] {
+                    if is_key_pressed(key) {
+                        widget.handle_event(&InputEvent::KeyPressed(key));
+                    }
+                }
+            }
+        }
+
+        // Remember where the focused object is so draw() can put the ring around it
+        self.focus_rect = self.focused.map(|index| widgets[index].bounds());
+    }
+
+    // Draw the focus ring around the focused object
+    pub fn draw(&self) {
+        if !self.from_keyboard && !self.ring_on_click {
+            return;
+        }
+
+        if let Some(rect) = self.focus_rect {
+            draw_rectangle_lines(
+                rect.x - self.ring_padding,
+                rect.y - self.ring_padding,
+                rect.w + self.ring_padding * 2.0,
+                rect.h + self.ring_padding * 2.0,
+                self.ring_thickness,
+                self.ring_color,
+            );
+        }
+    }
+
+    // Find the next (or previous) object that can take focus, wrapping around the list
+    fn next_focusable(&self, widgets: &[&mut dyn Widget], backward: bool) -> Option<usize> {
+        let count = widgets.len();
+        if count == 0 {
+            return None;
+        }
+
+        // With nothing focused, Tab starts at the first object and Shift+Tab at the last
+        let start = match self.focused {
+            Some(index) => index,
+            None if backward => 0,
+            None => count - 1,
+        };
+
+        (1..=count)
+            .map(|offset| {
+                if backward {
+                    (start + count - offset) % count
+                } else {
+                    (start + offset) % count
+                }
+            })
+            .find(|&index| can_focus(&*widgets[index]))
+    }
+
+    // Tell the old and new objects about the change
+    fn change_focus(&mut self, widgets: &mut [&mut dyn Widget], index: Option<usize>) {
+        if self.focused == index {
+            return;
+        }
+
+        if let Some(widget) = self.focused.and_then(|old| widgets.get_mut(old)) {
+            widget.set_focused(false);
+        }
+
+        if let Some(new) = index {
+            widgets[new].set_focused(true);
+        }
+
+        self.focused = index;
+    }
+}
+
+// Objects must be focusable, visible and enabled to take focus
+fn can_focus(widget: &dyn Widget) -> bool {
+    widget.is_focusable() && widget.is_visible() && widget.is_enabled()
+}
@@ -6,6 +6,7 @@ Program Details: Adds a button object with image support and preloading
 To import you need:
 In your ui.rs file add the following to the end of the file:
     pub mod image_button;
+    pub mod events;
 
 Then add the following with the use commands:
     use crate::ui::image_button::ImageButton;
@@ -36,7 +37,16 @@ Usage examples:
         // Handle button click
     }
 
-4. Change button images:
+4. Press the button from code or the keyboard (used by the focus manager):
+    btn_image.activate();   // The next click() will return true
+
+5. Run code when something happens (needs pub mod events; in ui.rs, see events.rs):
+    btn_image.on_click(|| println!("Clicked"));
+    btn_image.on_hover_enter(|| println!("Mouse is over the button"));
+    btn_image.on_hover_leave(|| println!("Mouse left the button"));
+    btn_image.with_event_queue(&events, "shop");  // Or send the events to a queue
+
+6. Change button images:
     // Change both normal and hover images
     btn_image.set_image(
         "assets/new_button.png",
@@ -50,6 +60,9 @@ Usage examples:
 
 use macroquad::prelude::*;
 use macroquad::texture::Texture2D;
+use std::cell::Cell;
+
+use crate::ui::events::{EventHandlers, EventQueue};
 #[cfg(feature = "scale")]
 use crate::modules::scale::mouse_position_world as mouse_position;
 
@@ -66,6 +79,8 @@ pub struct ImageButton {
     tex_height: usize,
     pub visible: bool,
     filename: String, // Adding filename field to track the current texture path
+    activated: Cell<bool>, // Set by activate() so the next click() returns true
+    events: EventHandlers, // Callbacks and event queue
 }
 
 impl ImageButton {
@@ -110,6 +125,8 @@ impl ImageButton {
             tex_height,
             visible: true,
             filename,
+            activated: Cell::new(false),
+            events: EventHandlers::new(),
         }
     }
 #[allow(unused)]
@@ -120,7 +137,7 @@ impl ImageButton {
         let hover_texture = load_texture(hover_texture_path).await.unwrap();
         let enabled = true;
         hover_texture.set_filter(FilterMode::Linear);
-        Self { x, y, width, height, enabled,texture, hover_texture, transparency_mask, tex_width, tex_height, visible: true, filename: texture_path.to_string() }
+        Self { x, y, width, height, enabled,texture, hover_texture, transparency_mask, tex_width, tex_height, visible: true, filename: texture_path.to_string(), activated: Cell::new(false), events: EventHandlers::new() }
     }
    
     /// Method to set new images for the button
@@ -176,8 +193,61 @@ impl ImageButton {
         }
     }
    
+    /// Press the button without the mouse, the next click() will return true
+    #[allow(unused)]
+    pub fn activate(&mut self) -> &mut Self {
+        if self.visible && self.enabled {
+            self.activated.set(true);
+        }
+        self
+    }
+
+    /// Run a function when the button is clicked
+    #[allow(unused)]
+    pub fn on_click<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_click(callback);
+        self
+    }
+
+    /// Run a function when the mouse moves onto the button
+    #[allow(unused)]
+    pub fn on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_enter(callback);
+        self
+    }
+
+    /// Run a function when the mouse moves off the button
+    #[allow(unused)]
+    pub fn on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_leave(callback);
+        self
+    }
+
+    /// Run a function when the button gains or loses keyboard focus
+    #[allow(unused)]
+    pub fn on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_focus(callback);
+        self
+    }
+
+    /// Send this button's events to a queue under the given id
+    #[allow(unused)]
+    pub fn with_event_queue(&mut self, queue: &EventQueue, id: &str) -> &mut Self {
+        self.events.set_queue(queue, id);
+        self
+    }
+
+    /// Called by the focus manager when the button gains or loses focus
+    #[allow(unused)]
+    pub fn set_focused(&mut self, focused: bool) -> &mut Self {
+        self.events.focus(focused);
+        self
+    }
+
     pub fn click(&self) -> bool {
         if !self.visible {
+            self.activated.set(false);
+            self.events.hover(false);
             return false; // If the button is not visible, don't process clicks
         }
         let (mouse_x, mouse_y) = mouse_position();
@@ -185,6 +255,7 @@ impl ImageButton {
 
         let rect = Rect::new(self.x, self.y, self.width, self.height);
         let is_hovered = rect.contains(mouse_pos);
+        self.events.hover(is_hovered);
 
         let texture_to_draw = if self.enabled && is_hovered && self.is_hovered(mouse_x, mouse_y) {
             &self.hover_texture
@@ -211,7 +282,13 @@ impl ImageButton {
             },
         );
 
-        is_hovered && self.enabled && is_mouse_button_pressed(MouseButton::Left)
+        // Keyboard activation counts as a click
+        let activated = self.activated.replace(false);
+        let clicked = self.enabled && ((is_hovered && is_mouse_button_pressed(MouseButton::Left)) || activated);
+        if clicked {
+            self.events.click();
+        }
+        clicked
     }
 
     fn is_hovered(&self, mouse_x: f32, mouse_y: f32) -> bool {
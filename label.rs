@@ -6,6 +6,7 @@ Adds a label object
 
 In your ui.rs file located in the root:
         pub mod label;
+        pub mod theme;
     
 
 Add with the other use statements
@@ -81,6 +82,8 @@ Then in the loop you would use:
 */
 use macroquad::prelude::*;
 
+use crate::ui::theme::{Theme, current_theme};
+
 pub struct Label {
     text: String,
     x: f32,
@@ -119,19 +122,20 @@ pub enum TextAlign {
 impl Label {
     // Constructor using x and y separately
     pub fn new<T: Into<String>>(text: T, x: f32, y: f32, font_size: u16) -> Self {
+        let theme = current_theme();
         let mut label = Self {
             text: text.into(),
             x,
             y,
             font_size,
-            foreground: BLACK, // Default to black
+            foreground: theme.text, // Default text color from the theme
             background: None,  // No background by default
             line_spacing: 1.2,
-            font: None,        // Default to None (use system font)
-            corner_radius: 0.0, // Default to no rounded corners
+            font: theme.font.clone(), // Theme font (None uses the system font)
+            corner_radius: theme.corner_radius, // Theme rounding (0 is square)
             border: false,      // Default to no border
-            border_color: BLACK, // Default border color
-            border_thickness: 1.0, // Default border thickness
+            border_color: theme.border, // Default border color
+            border_thickness: theme.border_thickness, // Default border thickness
             visible: true,      // Default to visible
             fixed_width: None, // No fixed width by default
             fixed_height: None, // No fixed height by default
@@ -278,6 +282,20 @@ impl Label {
         chunks
     }
 
+    // Restyle the label with a theme (keeps the background and font size)
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.foreground = theme.text;
+        self.border_color = theme.border;
+        self.border_thickness = theme.border_thickness;
+        self.corner_radius = theme.corner_radius;
+        if let Some(font) = &theme.font {
+            self.font = Some(font.clone());
+        }
+        self.calculate_text_dimensions();
+        self
+    }
+
     // Method to set foreground and background colors
     #[allow(unused)]
     pub fn with_colors(&mut self, foreground: Color, background: Option<Color>) -> &mut Self {
@@ -0,0 +1,546 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Adds layout containers (VBox, HBox, Grid) and anchoring so objects
+can be placed automatically instead of with hand picked x, y values
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod widget;
+    pub mod layout;
+
+Then add the following with the use commands:
+    use crate::ui::layout::{VBox, HBox, Grid, Anchor, Align, anchor_widget, place_relative};
+
+Any object that implements Widget (buttons, labels, sliders, lists, images...) can
+be placed by a layout. The layout does not own the objects, you keep using them
+like normal and just pass them in when arranging.
+
+Then above the loop you would set up the layout:
+    let mut menu = VBox::new();
+    menu.with_anchor(Anchor::Center)   // Where the whole box sits on the screen
+        .with_spacing(20.0)            // Space between each object
+        .with_padding(10.0)            // Space around the outside of the objects
+        .with_align(Align::Center);    // Line objects up in the middle of the box
+
+    let mut toolbar = HBox::new();
+    toolbar.with_anchor(Anchor::Top).with_margin(10.0).with_spacing(5.0);
+
+    let mut board = Grid::new(3);      // 3 columns, rows are added as needed
+    board.with_anchor(Anchor::Left)
+         .with_cell_size(100.0, 100.0) // Leave this out to size cells to the biggest object
+         .with_spacing(4.0);
+
+Then inside the loop (after use_virtual_resolution if you use the scale module):
+    menu.arrange(&mut [&mut btn_play, &mut btn_options, &mut lbl_version]);
+    toolbar.arrange(&mut [&mut btn_back, &mut btn_help]);
+    board.arrange(&mut [&mut img_1, &mut img_2, &mut img_3, &mut img_4]);
+
+    // Single objects can also be pinned to a side or corner of the screen
+    anchor_widget(&mut btn_quit, Anchor::BottomRight, 20.0);
+
+    // Or placed by percent of the screen (0.5, 0.5 is the middle)
+    place_relative(&mut lbl_title, 0.5, 0.15);
+
+    // Then draw the objects as usual
+    if btn_play.click() { ... }
+
+Because the layout is worked out again each time arrange() is called, everything
+re-flows by itself when the window is resized or the virtual resolution changes.
+
+Layouts can be nested by giving one layout the area of another:
+    let area = board.cell_rect(4);     // Rect of the middle cell from the last arrange
+    inner_box.with_area(area).arrange(&mut [&mut lbl_a, &mut lbl_b]);
+
+Note: Hidden objects are skipped by VBox and HBox so the others close the gap.
+In a Grid they keep their cell so the other objects don't jump around.
+*/
+
+use macroquad::prelude::*;
+
+use crate::ui::widget::Widget;
+
+// How objects line up inside a layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(unused)]
+pub enum Align {
+    Start,  // Left (or top)
+    Center,
+    End,    // Right (or bottom)
+}
+
+// Where something is pinned inside an area
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(unused)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // Get the top-left corner for something of the given size pinned inside an area
+    // The margin keeps it away from the edges it is pinned to
+    pub fn place(&self, area: Rect, size: Vec2, margin: f32) -> Vec2 {
+        let (horizontal, vertical) = match self {
+            Anchor::TopLeft => (Align::Start, Align::Start),
+            Anchor::Top => (Align::Center, Align::Start),
+            Anchor::TopRight => (Align::End, Align::Start),
+            Anchor::Left => (Align::Start, Align::Center),
+            Anchor::Center => (Align::Center, Align::Center),
+            Anchor::Right => (Align::End, Align::Center),
+            Anchor::BottomLeft => (Align::Start, Align::End),
+            Anchor::Bottom => (Align::Center, Align::End),
+            Anchor::BottomRight => (Align::End, Align::End),
+        };
+
+        Vec2::new(
+            align_in(horizontal, area.x, area.w, size.x, margin),
+            align_in(vertical, area.y, area.h, size.y, margin),
+        )
+    }
+}
+
+// Work out the start of something of `size` inside a span starting at `start` of length `length`
+fn align_in(align: Align, start: f32, length: f32, size: f32, margin: f32) -> f32 {
+    match align {
+        Align::Start => start + margin,
+        Align::Center => start + (length - size) / 2.0,
+        Align::End => start + length - size - margin,
+    }
+}
+
+// The area layouts fill by default: the virtual resolution when using the scale
+// module, otherwise the window
+pub fn layout_area() -> Rect {
+    #[cfg(feature = "scale")]
+    {
+        if let Ok((virtual_width, virtual_height)) =
+            crate::utils::scale::VIRTUAL_RESOLUTION.try_with(|res| *res.borrow())
+        {
+            return Rect::new(0.0, 0.0, virtual_width, virtual_height);
+        }
+    }
+
+    Rect::new(0.0, 0.0, screen_width(), screen_height())
+}
+
+// Pin a single object to a side, corner or the center of the screen
+#[allow(unused)]
+pub fn anchor_widget(widget: &mut dyn Widget, anchor: Anchor, margin: f32) {
+    anchor_widget_in(widget, layout_area(), anchor, margin);
+}
+
+// Pin a single object inside a given area
+#[allow(unused)]
+pub fn anchor_widget_in(widget: &mut dyn Widget, area: Rect, anchor: Anchor, margin: f32) {
+    let bounds = widget.bounds();
+    let corner = anchor.place(area, Vec2::new(bounds.w, bounds.h), margin);
+    widget.move_to(corner.x, corner.y);
+}
+
+// Center an object on a point given as a fraction of the screen (0.0 to 1.0)
+#[allow(unused)]
+pub fn place_relative(widget: &mut dyn Widget, fraction_x: f32, fraction_y: f32) {
+    let area = layout_area();
+    let bounds = widget.bounds();
+    widget.move_to(
+        area.x + area.w * fraction_x - bounds.w / 2.0,
+        area.y + area.h * fraction_y - bounds.h / 2.0,
+    );
+}
+
+// Settings shared by VBox and HBox
+#[derive(Clone, Copy)]
+struct StackSettings {
+    anchor: Anchor,
+    area: Option<Rect>,
+    position: Option<Vec2>,
+    margin: f32,
+    padding: f32,
+    spacing: f32,
+    align: Align,
+}
+
+impl StackSettings {
+    fn new() -> Self {
+        Self {
+            anchor: Anchor::TopLeft,
+            area: None,
+            position: None,
+            margin: 0.0,
+            padding: 0.0,
+            spacing: 10.0,
+            align: Align::Start,
+        }
+    }
+}
+
+// Place objects one after another, returns the rectangle the whole stack covers
+fn arrange_stack(settings: &StackSettings, vertical: bool, widgets: &mut [&mut dyn Widget]) -> Rect {
+    // Measure the visible objects
+    let sizes: Vec<Option<Vec2>> = widgets
+        .iter()
+        .map(|widget| {
+            if widget.is_visible() {
+                let bounds = widget.bounds();
+                Some(Vec2::new(bounds.w, bounds.h))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let visible: Vec<Vec2> = sizes.iter().flatten().copied().collect();
+    let gaps = visible.len().saturating_sub(1) as f32 * settings.spacing;
+
+    let (content_width, content_height) = if vertical {
+        (
+            visible.iter().fold(0.0f32, |max, size| max.max(size.x)),
+            visible.iter().map(|size| size.y).sum::<f32>() + gaps,
+        )
+    } else {
+        (
+            visible.iter().map(|size| size.x).sum::<f32>() + gaps,
+            visible.iter().fold(0.0f32, |max, size| max.max(size.y)),
+        )
+    };
+
+    let total = Vec2::new(
+        content_width + settings.padding * 2.0,
+        content_height + settings.padding * 2.0,
+    );
+
+    // A fixed position wins over the anchor
+    let origin = match settings.position {
+        Some(position) => position,
+        None => settings.anchor.place(settings.area.unwrap_or_else(layout_area), total, settings.margin),
+    };
+
+    let mut cursor = if vertical {
+        origin.y + settings.padding
+    } else {
+        origin.x + settings.padding
+    };
+
+    for (widget, size) in widgets.iter_mut().zip(sizes.iter()) {
+        let Some(size) = size else {
+            continue;
+        };
+
+        if vertical {
+            let x = align_in(settings.align, origin.x + settings.padding, content_width, size.x, 0.0);
+            widget.move_to(x, cursor);
+            cursor += size.y + settings.spacing;
+        } else {
+            let y = align_in(settings.align, origin.y + settings.padding, content_height, size.y, 0.0);
+            widget.move_to(cursor, y);
+            cursor += size.x + settings.spacing;
+        }
+    }
+
+    Rect::new(origin.x, origin.y, total.x, total.y)
+}
+
+// Vertical stack: objects are placed top to bottom
+pub struct VBox {
+    settings: StackSettings,
+    bounds: Rect,
+}
+
+impl Default for VBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(unused)]
+impl VBox {
+    pub fn new() -> Self {
+        Self {
+            settings: StackSettings::new(),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    // Pin the box to part of its area (the screen unless with_area is used)
+    pub fn with_anchor(&mut self, anchor: Anchor) -> &mut Self {
+        self.settings.anchor = anchor;
+        self.settings.position = None;
+        self
+    }
+
+    // Place the box at a fixed top-left corner instead of anchoring it
+    pub fn with_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.settings.position = Some(Vec2::new(x, y));
+        self
+    }
+
+    // Use part of the screen (such as a grid cell) instead of the whole screen
+    pub fn with_area(&mut self, area: Rect) -> &mut Self {
+        self.settings.area = Some(area);
+        self
+    }
+
+    // Space between the box and the edges it is anchored to
+    pub fn with_margin(&mut self, margin: f32) -> &mut Self {
+        self.settings.margin = margin;
+        self
+    }
+
+    // Space between the edge of the box and the objects inside it
+    pub fn with_padding(&mut self, padding: f32) -> &mut Self {
+        self.settings.padding = padding;
+        self
+    }
+
+    // Space between each object
+    pub fn with_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.settings.spacing = spacing;
+        self
+    }
+
+    // How objects line up left to right (Start = left, End = right)
+    pub fn with_align(&mut self, align: Align) -> &mut Self {
+        self.settings.align = align;
+        self
+    }
+
+    // Position the objects, call this each frame so the box follows screen changes
+    pub fn arrange(&mut self, widgets: &mut [&mut dyn Widget]) -> Rect {
+        self.bounds = arrange_stack(&self.settings, true, widgets);
+        self.bounds
+    }
+
+    // The rectangle covered by the box the last time it was arranged
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+// Horizontal stack: objects are placed left to right
+pub struct HBox {
+    settings: StackSettings,
+    bounds: Rect,
+}
+
+impl Default for HBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(unused)]
+impl HBox {
+    pub fn new() -> Self {
+        Self {
+            settings: StackSettings::new(),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    // Pin the box to part of its area (the screen unless with_area is used)
+    pub fn with_anchor(&mut self, anchor: Anchor) -> &mut Self {
+        self.settings.anchor = anchor;
+        self.settings.position = None;
+        self
+    }
+
+    // Place the box at a fixed top-left corner instead of anchoring it
+    pub fn with_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.settings.position = Some(Vec2::new(x, y));
+        self
+    }
+
+    // Use part of the screen (such as a grid cell) instead of the whole screen
+    pub fn with_area(&mut self, area: Rect) -> &mut Self {
+        self.settings.area = Some(area);
+        self
+    }
+
+    // Space between the box and the edges it is anchored to
+    pub fn with_margin(&mut self, margin: f32) -> &mut Self {
+        self.settings.margin = margin;
+        self
+    }
+
+    // Space between the edge of the box and the objects inside it
+    pub fn with_padding(&mut self, padding: f32) -> &mut Self {
+        self.settings.padding = padding;
+        self
+    }
+
+    // Space between each object
+    pub fn with_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.settings.spacing = spacing;
+        self
+    }
+
+    // How objects line up top to bottom (Start = top, End = bottom)
+    pub fn with_align(&mut self, align: Align) -> &mut Self {
+        self.settings.align = align;
+        self
+    }
+
+    // Position the objects, call this each frame so the box follows screen changes
+    pub fn arrange(&mut self, widgets: &mut [&mut dyn Widget]) -> Rect {
+        self.bounds = arrange_stack(&self.settings, false, widgets);
+        self.bounds
+    }
+
+    // The rectangle covered by the box the last time it was arranged
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+// Grid: objects fill the cells left to right, then top to bottom
+pub struct Grid {
+    columns: usize,
+    cell_size: Option<Vec2>,
+    anchor: Anchor,
+    area: Option<Rect>,
+    position: Option<Vec2>,
+    margin: f32,
+    padding: f32,
+    spacing: f32,
+    align_x: Align,
+    align_y: Align,
+    cells: Vec<Rect>,
+    bounds: Rect,
+}
+
+#[allow(unused)]
+impl Grid {
+    pub fn new(columns: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            cell_size: None,
+            anchor: Anchor::TopLeft,
+            area: None,
+            position: None,
+            margin: 0.0,
+            padding: 0.0,
+            spacing: 10.0,
+            align_x: Align::Center,
+            align_y: Align::Center,
+            cells: Vec::new(),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    // Give every cell a fixed size (otherwise cells fit the biggest object)
+    pub fn with_cell_size(&mut self, width: f32, height: f32) -> &mut Self {
+        self.cell_size = Some(Vec2::new(width, height));
+        self
+    }
+
+    // Pin the grid to part of its area (the screen unless with_area is used)
+    pub fn with_anchor(&mut self, anchor: Anchor) -> &mut Self {
+        self.anchor = anchor;
+        self.position = None;
+        self
+    }
+
+    // Place the grid at a fixed top-left corner instead of anchoring it
+    pub fn with_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.position = Some(Vec2::new(x, y));
+        self
+    }
+
+    // Use part of the screen instead of the whole screen
+    pub fn with_area(&mut self, area: Rect) -> &mut Self {
+        self.area = Some(area);
+        self
+    }
+
+    // Space between the grid and the edges it is anchored to
+    pub fn with_margin(&mut self, margin: f32) -> &mut Self {
+        self.margin = margin;
+        self
+    }
+
+    // Space between the edge of the grid and the cells
+    pub fn with_padding(&mut self, padding: f32) -> &mut Self {
+        self.padding = padding;
+        self
+    }
+
+    // Space between cells
+    pub fn with_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.spacing = spacing;
+        self
+    }
+
+    // How objects line up inside their cell
+    pub fn with_align(&mut self, align_x: Align, align_y: Align) -> &mut Self {
+        self.align_x = align_x;
+        self.align_y = align_y;
+        self
+    }
+
+    // Position the objects, call this each frame so the grid follows screen changes
+    pub fn arrange(&mut self, widgets: &mut [&mut dyn Widget]) -> Rect {
+        let count = widgets.len();
+        let rows = count.div_ceil(self.columns);
+
+        // Work out the cell size from the biggest object if it wasn't set
+        let cell = self.cell_size.unwrap_or_else(|| {
+            widgets.iter().fold(Vec2::ZERO, |biggest, widget| {
+                let bounds = widget.bounds();
+                Vec2::new(biggest.x.max(bounds.w), biggest.y.max(bounds.h))
+            })
+        });
+
+        let columns = self.columns.min(count.max(1)) as f32;
+        let total = Vec2::new(
+            columns * cell.x + (columns - 1.0) * self.spacing + self.padding * 2.0,
+            rows as f32 * cell.y + (rows as f32 - 1.0).max(0.0) * self.spacing + self.padding * 2.0,
+        );
+
+        let origin = match self.position {
+            Some(position) => position,
+            None => self.anchor.place(self.area.unwrap_or_else(layout_area), total, self.margin),
+        };
+
+        self.cells.clear();
+        for (index, widget) in widgets.iter_mut().enumerate() {
+            let column = (index % self.columns) as f32;
+            let row = (index / self.columns) as f32;
+            let cell_rect = Rect::new(
+                origin.x + self.padding + column * (cell.x + self.spacing),
+                origin.y + self.padding + row * (cell.y + self.spacing),
+                cell.x,
+                cell.y,
+            );
+            self.cells.push(cell_rect);
+
+            if widget.is_visible() {
+                let bounds = widget.bounds();
+                widget.move_to(
+                    align_in(self.align_x, cell_rect.x, cell_rect.w, bounds.w, 0.0),
+                    align_in(self.align_y, cell_rect.y, cell_rect.h, bounds.h, 0.0),
+                );
+            }
+        }
+
+        self.bounds = Rect::new(origin.x, origin.y, total.x, total.y);
+        self.bounds
+    }
+
+    // The rectangle of a cell from the last arrange (empty if there is no such cell)
+    pub fn cell_rect(&self, index: usize) -> Rect {
+        self.cells.get(index).copied().unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0))
+    }
+
+    // The rectangle covered by the grid the last time it was arranged
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
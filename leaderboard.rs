@@ -0,0 +1,703 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: High score tables built on DatabaseClient. Submit scores, show the top
+players, find a player's rank, and keep daily, weekly, monthly and all time boards.
+Scores can be kept in a file while offline and sent later.
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod database;
+    pub mod leaderboard;
+And in your ui.rs file (only needed for show_in):
+    pub mod listview;
+
+Then add the following with the use commands:
+    use crate::utils::leaderboard::{Leaderboard, Period};
+
+The scores are kept in one table called leaderboard. Make it once, either with the SQL
+below in Turso (or your db_server schema):
+    CREATE TABLE IF NOT EXISTS leaderboard (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        board TEXT NOT NULL,
+        period TEXT NOT NULL,
+        player TEXT NOT NULL,
+        score REAL NOT NULL,
+        updated_at INTEGER NOT NULL,
+        UNIQUE(board, period, player)
+    );
+or from the game:
+    Leaderboard::create_table(&client).await.ok();
+The UNIQUE stops two devices both adding a row for the same player at the same moment.
+Firestore can't do that, so skip create_table there. A player who ends up with two rows
+anyway is listed once, at their best score.
+
+Then above the loop you would use:
+    let client = create_database_client();
+    let board = Leaderboard::new(&client, "level1");   // Each board name is its own high score list
+
+    // Optional settings
+    board.with_score_range(0.0, 1_000_000.0);           // Anything outside is refused
+    board.with_validator(|player, score| {               // Your own checks
+        if score as i64 % 10 != 0 { Err("Scores go up by 10".to_string()) } else { Ok(()) }
+    });
+    board.with_lower_is_better(true);                   // For race times, the lowest score wins
+    board.with_periods(&[Period::AllTime, Period::Daily]);  // Boards to keep (default all four)
+    board.with_local_file("level1_scores.json").ok();   // Keep scores while offline
+
+    // Submit a score, true when it beat the player's best on any of the boards
+    match board.submit("ann", 1200.0).await {
+        Ok(true) => println!("New best!"),
+        Ok(false) => {}
+        Err(DbError::Queued) => println!("Offline, the score will be sent later"),
+        Err(err) => error_box = MessageBox::info("Leaderboard", err.user_message()),
+    }
+
+    // The top 10 this week
+    let top = board.top(Period::Weekly, 10).await.unwrap_or_default();
+    for entry in &top {
+        println!("{}. {} {}", entry.rank, entry.player, entry.score);
+    }
+
+    // Where a player is on a board (None if they haven't played in that period)
+    if let Ok(Some(entry)) = board.player_entry("ann", Period::AllTime).await {
+        println!("You are number {}", entry.rank);
+    }
+
+    // Show the scores in a ListView, highlighting a player
+    let mut scores_list = ListView::new(&Vec::<String>::new(), 50.0, 100.0, 24);
+    scores_list.with_max_visible_items(10);
+    Leaderboard::show_in(&mut scores_list, &top, Some("ann"));
+
+Then inside the loop:
+    scores_list.draw();
+
+While offline (with with_local_file):
+    submit() keeps the score in the file and returns Err(DbError::Queued).
+    top() and player_entry() answer from the last scores that were downloaded plus the
+    scores made on this device, and board.is_offline() is true.
+    Call board.sync().await when back online to send the kept scores.
+
+Other options:
+    board.pending_scores();                  // Scores waiting to be sent
+    entry.line();                            // "1. ann  1200", the text show_in uses
+    Period::Daily.current_key();             // "day-2026-10-16", what is stored in the period column
+
+Note: Periods use UTC, so a new day starts at midnight UTC for everyone. Weeks start on
+Monday. Each player has one row per period holding their best score.
+A score is sent as one transaction covering every period. Postgres REST (db_providers.rs)
+refuses transactions with more than one write, so there use one period per Leaderboard:
+    let weekly = Leaderboard::new(&client, "level1");
+    weekly.with_periods(&[Period::Weekly]);
+*/
+
+use macroquad::miniquad::date;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::ui::listview::ListView;
+use crate::utils::database::{DatabaseClient, DbError, DbRecord, Query, Transaction};
+use crate::utils::textfiles::TextFile;
+
+// Longest player name that is accepted
+const MAX_NAME_LENGTH: usize = 32;
+
+crate::db_record! {
+    #[table("leaderboard", unique(board, period, player))]
+    /// One row of the leaderboard table, a player's best score on one board for one period
+    pub struct LeaderboardRow {
+        pub board: String,
+        pub period: String,
+        pub player: String,
+        pub score: f64,
+        pub updated_at: i64,
+    }
+}
+
+/// Which scores a board includes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Period {
+    AllTime,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[allow(unused)]
+impl Period {
+    pub const ALL: [Period; 4] = [Period::AllTime, Period::Daily, Period::Weekly, Period::Monthly];
+
+    /// The value of the period column for the period holding `time` (seconds since 1970, UTC)
+    pub fn key_at(&self, time: f64) -> String {
+        let day = (time / 86400.0).floor() as i64;
+        match self {
+            Period::AllTime => "all".to_string(),
+            Period::Daily => {
+                let (year, month, day) = civil_date(day);
+                format!("day-{:04}-{:02}-{:02}", year, month, day)
+            }
+            Period::Weekly => {
+                // Day 0 (Jan 1 1970) was a Thursday, so this goes back to the Monday
+                let (year, month, day) = civil_date(day - (day + 3).rem_euclid(7));
+                format!("week-{:04}-{:02}-{:02}", year, month, day)
+            }
+            Period::Monthly => {
+                let (year, month, _) = civil_date(day);
+                format!("month-{:04}-{:02}", year, month)
+            }
+        }
+    }
+
+    /// The value of the period column right now
+    pub fn current_key(&self) -> String {
+        self.key_at(date::now())
+    }
+}
+
+// Year, month and day for a count of days since Jan 1 1970
+fn civil_date(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// A place on a board
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub rank: usize, // 1 for first, tied scores share a rank
+    pub player: String,
+    pub score: f64,
+    pub updated_at: i64, // When the score was made (seconds since 1970)
+}
+
+#[allow(unused)]
+impl LeaderboardEntry {
+    /// The entry as one line of text, like "1. ann  1200"
+    pub fn line(&self) -> String {
+        format!("{}. {}  {}", self.rank, self.player, self.score)
+    }
+}
+
+// A score made while offline, waiting to be sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingScore {
+    player: String,
+    score: f64,
+    time: f64,
+}
+
+// What is kept in the local file
+#[derive(Default, Serialize, Deserialize)]
+struct LocalScores {
+    rows: Vec<LeaderboardRow>,     // The last rows downloaded, plus scores made here
+    pending: Vec<PendingScore>,
+}
+
+struct LocalFile {
+    path: String,
+    scores: LocalScores,
+}
+
+impl LocalFile {
+    fn save(&self) -> Result<(), DbError> {
+        let json = serde_json::to_string(&self.scores).map_err(|e| DbError::Storage(e.to_string()))?;
+        TextFile::backend().write(&self.path, &json).map_err(DbError::Storage)
+    }
+}
+
+// Shorter name for the validator type
+type Validator = Rc<dyn Fn(&str, f64) -> Result<(), String>>;
+
+/// A high score table, many boards can share one DatabaseClient
+pub struct Leaderboard {
+    client: DatabaseClient,
+    board: String,
+    periods: RefCell<Vec<Period>>,
+    lower_is_better: Cell<bool>,
+    min_score: Cell<f64>,
+    max_score: Cell<f64>,
+    validator: RefCell<Option<Validator>>,
+    local: RefCell<Option<LocalFile>>,
+    offline: Cell<bool>,
+}
+
+#[allow(unused)]
+impl Leaderboard {
+    pub fn new(client: &DatabaseClient, board: &str) -> Self {
+        Self {
+            client: client.clone(),
+            board: board.to_string(),
+            periods: RefCell::new(Period::ALL.to_vec()),
+            lower_is_better: Cell::new(false),
+            min_score: Cell::new(f64::MIN),
+            max_score: Cell::new(f64::MAX),
+            validator: RefCell::new(None),
+            local: RefCell::new(None),
+            offline: Cell::new(false),
+        }
+    }
+
+    /// Make the leaderboard table if it isn't there yet
+    pub async fn create_table(client: &DatabaseClient) -> Result<(), DbError> {
+        client.create_table_if_missing::<LeaderboardRow>().await
+    }
+
+    pub fn board(&self) -> &str {
+        &self.board
+    }
+
+    /// Scores below min or above max are refused by submit()
+    pub fn with_score_range(&self, min: f64, max: f64) -> &Self {
+        self.min_score.set(min);
+        self.max_score.set(max);
+        self
+    }
+
+    /// Extra checks run by submit(), return Err with a reason to refuse the score
+    pub fn with_validator<F: Fn(&str, f64) -> Result<(), String> + 'static>(&self, validator: F) -> &Self {
+        *self.validator.borrow_mut() = Some(Rc::new(validator));
+        self
+    }
+
+    /// For times and other scores where the lowest is best
+    pub fn with_lower_is_better(&self, lower_is_better: bool) -> &Self {
+        self.lower_is_better.set(lower_is_better);
+        self
+    }
+
+    /// Which boards submit() updates
+    pub fn with_periods(&self, periods: &[Period]) -> &Self {
+        *self.periods.borrow_mut() = periods.to_vec();
+        self
+    }
+
+    /// Keep scores in a file (localStorage on web) so they survive being offline
+    pub fn with_local_file(&self, path: &str) -> Result<&Self, DbError> {
+        let saved = TextFile::backend().read(path).map_err(DbError::Storage)?;
+        let scores = match saved {
+            Some(json) => serde_json::from_str(&json).map_err(|e| DbError::Storage(e.to_string()))?,
+            None => LocalScores::default(),
+        };
+        *self.local.borrow_mut() = Some(LocalFile {
+            path: path.to_string(),
+            scores,
+        });
+        Ok(self)
+    }
+
+    /// True when the last top() or player_entry() answered from the local file
+    pub fn is_offline(&self) -> bool {
+        self.offline.get()
+    }
+
+    /// How many scores made offline are waiting for sync()
+    pub fn pending_scores(&self) -> usize {
+        self.local.borrow().as_ref().map_or(0, |local| local.scores.pending.len())
+    }
+
+    /// Check a score without sending it
+    pub fn validate(&self, player: &str, score: f64) -> Result<(), DbError> {
+        if player.trim().is_empty() {
+            return Err(DbError::Invalid("Player name is empty".to_string()));
+        }
+        if player.chars().count() > MAX_NAME_LENGTH {
+            return Err(DbError::Invalid(format!("Player names can be up to {} characters", MAX_NAME_LENGTH)));
+        }
+        if !score.is_finite() || score < self.min_score.get() || score > self.max_score.get() {
+            return Err(DbError::Invalid(format!("Score {} is not allowed", score)));
+        }
+        let validator = self.validator.borrow().clone();
+        if let Some(validator) = validator {
+            validator(player, score).map_err(DbError::Invalid)?;
+        }
+        Ok(())
+    }
+
+    /// Submit a score, returns true when it beat the player's best on at least one board
+    /// With with_local_file, a score that can't be sent is kept and Err(DbError::Queued) is returned
+    pub async fn submit(&self, player: &str, score: f64) -> Result<bool, DbError> {
+        self.validate(player, score)?;
+        let time = date::now();
+
+        let result = self.send_score(player, score, time).await;
+        self.keep_locally(player, score, time);
+        match result {
+            Err(err) if err.is_retryable() && self.local.borrow().is_some() => {
+                self.with_local(|local| {
+                    local.scores.pending.push(PendingScore {
+                        player: player.to_string(),
+                        score,
+                        time,
+                    })
+                })?;
+                Err(DbError::Queued)
+            }
+            // The client's own offline queue took the write
+            Err(DbError::Queued) => Err(DbError::Queued),
+            Err(err) => Err(err),
+            Ok(improved) => Ok(improved),
+        }
+    }
+
+    /// Send the scores made offline, returns how many were sent
+    /// Stops at the first one that fails, the rest are tried next time
+    pub async fn sync(&self) -> Result<usize, DbError> {
+        let pending = self.local.borrow().as_ref().map(|local| local.scores.pending.clone()).unwrap_or_default();
+        let mut sent = 0;
+        for score in &pending {
+            let result = match self.send_score(&score.player, score.score, score.time).await {
+                Err(err) if err.is_retryable() || err == DbError::Cancelled => break,
+                // The client's own offline queue has it now
+                Err(DbError::Queued) => Ok(()),
+                result => result.map(|_| ()),
+            };
+            // A score the server refused is dropped so it doesn't block the rest
+            self.with_local(|local| {
+                local.scores.pending.remove(0);
+            })?;
+            result?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    /// The best `count` scores on a board, best first
+    pub async fn top(&self, period: Period, count: usize) -> Result<Vec<LeaderboardEntry>, DbError> {
+        let key = period.current_key();
+        let mut query = Query::new();
+        query.where_eq("board", self.board.as_str()).where_eq("period", key.as_str());
+        self.order_best_first(&mut query);
+        query.limit(count as u32);
+
+        match self.client.fetch_query::<LeaderboardRow>(LeaderboardRow::table_name(), &query).await {
+            Ok(rows) => {
+                self.offline.set(false);
+                self.remember_rows(&key, &rows)?;
+                Ok(self.ranked(rows))
+            }
+            Err(err) if err.is_retryable() && self.local.borrow().is_some() => {
+                self.offline.set(true);
+                let mut rows = self.local_rows(&key);
+                rows.truncate(count);
+                Ok(self.ranked(rows))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// A player's place on a board, None if they have no score in that period
+    pub async fn player_entry(&self, player: &str, period: Period) -> Result<Option<LeaderboardEntry>, DbError> {
+        let key = period.current_key();
+        match self.fetch_entry(player, &key).await {
+            Ok(entry) => {
+                self.offline.set(false);
+                Ok(entry)
+            }
+            Err(err) if err.is_retryable() && self.local.borrow().is_some() => {
+                self.offline.set(true);
+                let rows = self.local_rows(&key);
+                Ok(self.ranked(rows).into_iter().find(|entry| entry.player == player))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Put entries into a ListView, selecting `highlight`'s line if they are on it
+    pub fn show_in(list: &mut ListView, entries: &[LeaderboardEntry], highlight: Option<&str>) {
+        list.clear();
+        list.add_items(&entries.iter().map(|entry| entry.line()).collect::<Vec<String>>());
+        let selected = highlight.and_then(|player| entries.iter().position(|entry| entry.player == player));
+        list.select_item(selected);
+    }
+
+    // Two small requests: the player's best row, then a count of the better scores
+    async fn fetch_entry(&self, player: &str, key: &str) -> Result<Option<LeaderboardEntry>, DbError> {
+        let mut query = Query::new();
+        query.where_eq("board", self.board.as_str()).where_eq("period", key).where_eq("player", player);
+        self.order_best_first(&mut query);
+        query.limit(1);
+        let Some(row) = self.client.fetch_query::<LeaderboardRow>(LeaderboardRow::table_name(), &query).await?.pop() else {
+            return Ok(None);
+        };
+
+        // The rank is one more than the number of better scores
+        let mut better = Query::new();
+        better.where_eq("board", self.board.as_str()).where_eq("period", key);
+        if self.lower_is_better.get() {
+            better.where_lt("score", row.score);
+        } else {
+            better.where_gt("score", row.score);
+        }
+        let ahead = self.client.count_query(LeaderboardRow::table_name(), &better).await?;
+        Ok(Some(self.entry(ahead as usize + 1, row)))
+    }
+
+    // Updates the player's row for each period if the score beats it
+    // If another device added one of the rows first, the UNIQUE refuses ours (409),
+    // so the rows are read again and the score is sent as an update instead
+    async fn send_score(&self, player: &str, score: f64, time: f64) -> Result<bool, DbError> {
+        match self.write_score(player, score, time).await {
+            Err(DbError::Http { status: 409, .. }) => self.write_score(player, score, time).await,
+            result => result,
+        }
+    }
+
+    async fn write_score(&self, player: &str, score: f64, time: f64) -> Result<bool, DbError> {
+        let keys: Vec<String> = self.periods.borrow().iter().map(|period| period.key_at(time)).collect();
+        let mut transaction = Transaction::new();
+        for key in &keys {
+            // Only the player's best row for this period, older periods aren't downloaded
+            let mut query = Query::new();
+            query.where_eq("board", self.board.as_str()).where_eq("period", key.as_str()).where_eq("player", player);
+            self.order_best_first(&mut query);
+            query.limit(1);
+            match self.client.fetch_query::<LeaderboardRow>(LeaderboardRow::table_name(), &query).await?.pop() {
+                Some(row) if !self.is_better(score, row.score) => {}
+                Some(row) => {
+                    transaction.update(LeaderboardRow::table_name(), &LeaderboardRow {
+                        score,
+                        updated_at: time as i64,
+                        ..row
+                    });
+                }
+                None => {
+                    transaction.insert(LeaderboardRow::table_name(), &self.row(key, player, score, time));
+                }
+            }
+        }
+
+        let improved = !transaction.is_empty();
+        self.client.run_transaction(&transaction).await?;
+        Ok(improved)
+    }
+
+    // Adds a score to the local rows (if there is a local file)
+    fn keep_locally(&self, player: &str, score: f64, time: f64) {
+        if self.local.borrow().is_none() {
+            return;
+        }
+        let keys: Vec<String> = self.periods.borrow().iter().map(|period| period.key_at(time)).collect();
+        let _ = self.with_local(|local| {
+            for key in &keys {
+                let rows = &mut local.scores.rows;
+                match rows.iter_mut().find(|row| row.board == self.board && row.period == *key && row.player == player) {
+                    Some(row) if !self.is_better(score, row.score) => {}
+                    Some(row) => {
+                        row.score = score;
+                        row.updated_at = time as i64;
+                    }
+                    None => rows.push(self.row(key, player, score, time)),
+                }
+            }
+        });
+    }
+
+    // Swaps in freshly downloaded rows for a period, keeping scores that haven't been sent yet
+    fn remember_rows(&self, key: &str, rows: &[LeaderboardRow]) -> Result<(), DbError> {
+        if self.local.borrow().is_none() {
+            return Ok(());
+        }
+        let pending = self.with_local(|local| {
+            local.scores.rows.retain(|row| row.board != self.board || row.period != key);
+            local.scores.rows.extend(rows.iter().cloned());
+            local.scores.pending.clone()
+        })?;
+        for score in pending {
+            self.keep_locally(&score.player, score.score, score.time);
+        }
+        Ok(())
+    }
+
+    // Local rows for a period, best first
+    fn local_rows(&self, key: &str) -> Vec<LeaderboardRow> {
+        let mut rows: Vec<LeaderboardRow> = self
+            .local
+            .borrow()
+            .iter()
+            .flat_map(|local| local.scores.rows.iter())
+            .filter(|row| row.board == self.board && row.period == key)
+            .cloned()
+            .collect();
+        rows.sort_by(|a, b| {
+            let order = if self.lower_is_better.get() { a.score.total_cmp(&b.score) } else { b.score.total_cmp(&a.score) };
+            order.then(a.updated_at.cmp(&b.updated_at))
+        });
+        rows
+    }
+
+    // Runs a change on the local scores and saves them
+    fn with_local<R>(&self, change: impl FnOnce(&mut LocalFile) -> R) -> Result<R, DbError> {
+        let mut local = self.local.borrow_mut();
+        let Some(local) = local.as_mut() else {
+            return Err(DbError::Storage("No local file, call with_local_file first".to_string()));
+        };
+        let result = change(local);
+        local.save()?;
+        Ok(result)
+    }
+
+    // Rows that are already best first, with ranks added (tied scores share a rank)
+    // A player with more than one row is only listed at their best
+    fn ranked(&self, rows: Vec<LeaderboardRow>) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(rows.len());
+        for row in rows {
+            if entries.iter().any(|entry| entry.player == row.player) {
+                continue;
+            }
+            let rank = match entries.last() {
+                Some(last) if last.score == row.score => last.rank,
+                _ => entries.len() + 1,
+            };
+            entries.push(self.entry(rank, row));
+        }
+        entries
+    }
+
+    fn entry(&self, rank: usize, row: LeaderboardRow) -> LeaderboardEntry {
+        LeaderboardEntry {
+            rank,
+            player: row.player,
+            score: row.score,
+            updated_at: row.updated_at,
+        }
+    }
+
+    fn row(&self, key: &str, player: &str, score: f64, time: f64) -> LeaderboardRow {
+        LeaderboardRow {
+            id: 0,
+            board: self.board.clone(),
+            period: key.to_string(),
+            player: player.to_string(),
+            score,
+            updated_at: time as i64,
+        }
+    }
+
+    // Best score first, the earlier score first when tied
+    fn order_best_first(&self, query: &mut Query) {
+        if self.lower_is_better.get() {
+            query.order_by("score");
+        } else {
+            query.order_by_desc("score");
+        }
+        query.order_by("updated_at");
+    }
+
+    fn is_better(&self, score: f64, best: f64) -> bool {
+        if self.lower_is_better.get() {
+            score < best
+        } else {
+            score > best
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::db_local::create_memory_client;
+    use crate::utils::textfiles::test_helpers::block_on;
+
+    const DAY: f64 = 86400.0;
+
+    fn keys_at(time: f64) -> Vec<String> {
+        Period::ALL.iter().map(|period| period.key_at(time)).collect()
+    }
+
+    fn row(player: &str, score: f64) -> LeaderboardRow {
+        LeaderboardRow {
+            id: 0,
+            board: "level1".to_string(),
+            period: "all".to_string(),
+            player: player.to_string(),
+            score,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn civil_date_matches_the_calendar() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(-1), (1969, 12, 31));
+        assert_eq!(civil_date(11016), (2000, 2, 29));
+        assert_eq!(civil_date(-25508), (1900, 3, 1));
+    }
+
+    #[test]
+    fn keys_change_at_the_period_boundaries() {
+        // Jan 1 1970 was a Thursday, its week started on Monday Dec 29 1969
+        assert_eq!(keys_at(0.0), ["all", "day-1970-01-01", "week-1969-12-29", "month-1970-01"]);
+        assert_eq!(keys_at(DAY - 1.0), ["all", "day-1970-01-01", "week-1969-12-29", "month-1970-01"]);
+        assert_eq!(keys_at(DAY), ["all", "day-1970-01-02", "week-1969-12-29", "month-1970-01"]);
+        assert_eq!(keys_at(4.0 * DAY - 1.0), ["all", "day-1970-01-04", "week-1969-12-29", "month-1970-01"]);
+        assert_eq!(keys_at(4.0 * DAY), ["all", "day-1970-01-05", "week-1970-01-05", "month-1970-01"]);
+        assert_eq!(keys_at(31.0 * DAY), ["all", "day-1970-02-01", "week-1970-01-26", "month-1970-02"]);
+
+        // Before 1970 the time is negative, a second before midnight is still the day before
+        assert_eq!(keys_at(-1.0), ["all", "day-1969-12-31", "week-1969-12-29", "month-1969-12"]);
+        assert_eq!(keys_at(-3.0 * DAY), ["all", "day-1969-12-29", "week-1969-12-29", "month-1969-12"]);
+        assert_eq!(keys_at(-3.0 * DAY - 1.0), ["all", "day-1969-12-28", "week-1969-12-22", "month-1969-12"]);
+
+        // Monday Mar 4 2024, the week before it runs over the leap day
+        assert_eq!(keys_at(19786.0 * DAY), ["all", "day-2024-03-04", "week-2024-03-04", "month-2024-03"]);
+        assert_eq!(keys_at(19786.0 * DAY - 1.0), ["all", "day-2024-03-03", "week-2024-02-26", "month-2024-03"]);
+    }
+
+    #[test]
+    fn ranked_shares_ranks_and_lists_players_once() {
+        let board = Leaderboard::new(&create_memory_client(), "level1");
+        let rows = vec![row("Ann", 50.0), row("Bob", 40.0), row("Cara", 40.0), row("Ann", 30.0), row("Dan", 20.0)];
+        let entries: Vec<(usize, String)> = board.ranked(rows).into_iter().map(|entry| (entry.rank, entry.player)).collect();
+        let expected = [(1, "Ann"), (2, "Bob"), (2, "Cara"), (4, "Dan")];
+        assert_eq!(entries, expected.map(|(rank, player)| (rank, player.to_string())));
+    }
+
+    #[test]
+    fn validate_refuses_bad_names_and_scores() {
+        let board = Leaderboard::new(&create_memory_client(), "level1");
+        board
+            .with_score_range(0.0, 100.0)
+            .with_validator(|player, score| if player == "cheater" && score > 0.0 { Err("No".to_string()) } else { Ok(()) });
+
+        assert!(board.validate("Ann", 100.0).is_ok());
+        assert!(board.validate(&"a".repeat(MAX_NAME_LENGTH), 0.0).is_ok());
+        assert!(board.validate(&"a".repeat(MAX_NAME_LENGTH + 1), 0.0).is_err());
+        assert!(board.validate("  ", 10.0).is_err());
+        assert!(board.validate("Ann", 100.5).is_err());
+        assert!(board.validate("Ann", -1.0).is_err());
+        assert!(board.validate("Ann", f64::NAN).is_err());
+        assert!(board.validate("Ann", f64::INFINITY).is_err());
+        assert_eq!(board.validate("cheater", 5.0), Err(DbError::Invalid("No".to_string())));
+        assert!(board.validate("cheater", 0.0).is_ok());
+    }
+
+    #[test]
+    fn submit_keeps_the_best_score() {
+        let client = create_memory_client();
+        block_on(client.create_table_if_missing::<LeaderboardRow>()).unwrap();
+        let board = Leaderboard::new(&client, "level1");
+        // Only all time, so the test can't cross midnight
+        board.with_periods(&[Period::AllTime]);
+
+        assert!(block_on(board.submit("Ann", 50.0)).unwrap());
+        assert!(!block_on(board.submit("Ann", 40.0)).unwrap());
+        assert!(block_on(board.submit("Bob", 45.0)).unwrap());
+        assert!(block_on(board.submit("Ann", 60.0)).unwrap());
+
+        let top = block_on(board.top(Period::AllTime, 10)).unwrap();
+        let scores: Vec<(&str, f64)> = top.iter().map(|entry| (entry.player.as_str(), entry.score)).collect();
+        assert_eq!(scores, [("Ann", 60.0), ("Bob", 45.0)]);
+        let bob = block_on(board.player_entry("Bob", Period::AllTime)).unwrap().unwrap();
+        assert_eq!(bob.rank, 2);
+        assert_eq!(block_on(client.fetch_table::<LeaderboardRow>(LeaderboardRow::table_name())).unwrap().len(), 2);
+    }
+}
@@ -6,6 +6,8 @@ Date: Jul 31 2026
 === SETUP ===
 1. Add to ui.rs:
    pub mod listview;
+   pub mod theme;
+   pub mod events;
 
 2. Add import in main.rs:
    use crate::ui::listview::ListView;
@@ -54,6 +56,20 @@ This will:
     list_view.remove_item(index);                        // Remove by index
     list_view.select_item(Some(index));                  // Select an item
     let selected = list_view.selected_item();            // Get selected item
+    list_view.select_next();                             // Move selection down one
+    list_view.select_previous();                         // Move selection up one
+
+=== EVENTS (needs pub mod events; in ui.rs) ===
+    list_view.on_select(|index, item| println!("{} {}", index, item)); // User picked an item
+    list_view.on_hover_enter(|| println!("Mouse over list"));
+    list_view.on_hover_leave(|| println!("Mouse left list"));
+    list_view.with_event_queue(&events, "levels");       // Or send events to a queue
+
+=== POSITION AND STATE ===
+    list_view.set_position(50.0, 80.0);                  // Move the list
+    let bounds = list_view.get_bounds();                 // Rect covering the whole list
+    list_view.set_visible(false);                        // Hide the list
+    list_view.set_enabled(false);                        // Ignore mouse input
 
 === COMPLETE EXAMPLE ===
     let mut items = vec!["Item 1".to_string(), "Item 2".to_string()];
@@ -80,6 +96,8 @@ This will:
 */
 
 use macroquad::prelude::*;
+use crate::ui::events::{EventHandlers, EventQueue};
+use crate::ui::theme::{Theme, current_theme};
 #[cfg(feature = "scale")]
 use crate::utils::scale::mouse_position_world as mouse_position;
 
@@ -106,19 +124,23 @@ pub struct ListView {
     border: bool,
     border_color: Color,
     border_thickness: f32,
+    visible: bool,
+    enabled: bool,
+    events: EventHandlers,
 }
 
 impl ListView {
     // Constructor with a vector of strings (takes a reference to avoid taking ownership)
     pub fn new<T: ToString + Clone>(items: &Vec<T>, x: f32, y: f32, font_size: u16) -> Self {
+        let theme = current_theme();
         Self {
             items: items.iter().map(|item| item.to_string()).collect(),
             x,
             y,
             font_size,
-            foreground: BLACK, // Default text color
+            foreground: theme.text, // Default text color
             background: None,  // No background by default
-            selection_color: Some(SKYBLUE), // Default selection color
+            selection_color: Some(theme.highlight), // Default selection color
             selected_index: None,
             item_spacing: 1.2, // Default line spacing
             item_padding: 5.0, // Default padding
@@ -129,12 +151,28 @@ impl ListView {
             scrollbar_color: Color::new(0.7, 0.7, 0.7, 0.7), // Light gray, semi-transparent
             scrollbar_handle_color: Color::new(0.5, 0.5, 0.5, 0.8), // Darker gray
             width_override: None,
-            font: None,
+            font: theme.font.clone(),
             border: false, // Default to no border
-            border_color: BLACK, // Default border color
-            border_thickness: 1.0, // Default border thickness
+            border_color: theme.border, // Default border color
+            border_thickness: theme.border_thickness, // Default border thickness
+            visible: true,
+            enabled: true,
+            events: EventHandlers::new(),
+        }
+    }
+    /// Restyle the list with a theme (keeps the background)
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.foreground = theme.text;
+        self.selection_color = Some(theme.highlight);
+        self.border_color = theme.border;
+        self.border_thickness = theme.border_thickness;
+        if let Some(font) = &theme.font {
+            self.font = Some(font.clone());
         }
+        self
     }
+
     /// Add a border with custom color and thickness
     #[allow(unused)]
     pub fn with_border(&mut self, color: Color, thickness: f32) -> &mut Self {
@@ -269,6 +307,150 @@ impl ListView {
         }
     }
 
+    // Run a function when the user selects an item (gets the index and the item text)
+    #[allow(unused)]
+    pub fn on_select<F: Fn(usize, &str) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_select(callback);
+        self
+    }
+
+    // Run a function when the mouse moves onto the list
+    #[allow(unused)]
+    pub fn on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_enter(callback);
+        self
+    }
+
+    // Run a function when the mouse moves off the list
+    #[allow(unused)]
+    pub fn on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_leave(callback);
+        self
+    }
+
+    // Run a function when the list gains or loses keyboard focus
+    #[allow(unused)]
+    pub fn on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_focus(callback);
+        self
+    }
+
+    // Send this list's events to a queue under the given id
+    #[allow(unused)]
+    pub fn with_event_queue(&mut self, queue: &EventQueue, id: &str) -> &mut Self {
+        self.events.set_queue(queue, id);
+        self
+    }
+
+    // Called by the focus manager when the list gains or loses focus
+    #[allow(unused)]
+    pub fn set_focused(&mut self, focused: bool) -> &mut Self {
+        self.events.focus(focused);
+        self
+    }
+
+    // Send the select event if the selection changed from `old_index`
+    fn notify_select(&self, old_index: Option<usize>) {
+        if self.selected_index == old_index {
+            return;
+        }
+        if let Some(index) = self.selected_index {
+            self.events.select(index, &self.items[index]);
+        }
+    }
+
+    // Move the selection down one item (used for arrow keys)
+    #[allow(unused)]
+    pub fn select_next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let next = match self.selected_index {
+            Some(index) => (index + 1).min(self.items.len() - 1),
+            None => 0,
+        };
+        let old_index = self.selected_index;
+        self.select_item(Some(next));
+        self.notify_select(old_index);
+    }
+
+    // Move the selection up one item (used for arrow keys)
+    #[allow(unused)]
+    pub fn select_previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let previous = match self.selected_index {
+            Some(index) => index.saturating_sub(1),
+            None => 0,
+        };
+        let old_index = self.selected_index;
+        self.select_item(Some(previous));
+        self.notify_select(old_index);
+    }
+
+    // Getter for x position
+    #[allow(unused)]
+    pub fn get_x(&self) -> f32 {
+        self.x
+    }
+
+    // Getter for y position
+    #[allow(unused)]
+    pub fn get_y(&self) -> f32 {
+        self.y
+    }
+
+    // Setter for position
+    #[allow(unused)]
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    // Get the rectangle the list occupies on screen (including the scrollbar when shown)
+    #[allow(unused)]
+    pub fn get_bounds(&self) -> Rect {
+        let (width, height) = self.calculate_dimensions();
+        let total_width = width + if self.show_scrollbar && self.max_visible_items.is_some() &&
+                               self.items.len() > self.max_visible_items.unwrap() {
+            self.scrollbar_width
+        } else {
+            0.0
+        };
+        Rect::new(
+            self.x - self.item_padding,
+            self.y - self.font_size as f32 + self.item_padding,
+            total_width,
+            height,
+        )
+    }
+
+    // Visibility getter and setter
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(unused)]
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        self
+    }
+
+    // Enabled getter and setter (a disabled list still draws but ignores the mouse)
+    #[allow(unused)]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[allow(unused)]
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
     // Calculate dimensions based on content
     fn calculate_dimensions(&self) -> (f32, f32) {
         let item_height = self.font_size as f32 * self.item_spacing;
@@ -367,6 +549,10 @@ impl ListView {
 
     // Method to handle click and update selection
     fn update(&mut self) {
+        let old_index = self.selected_index;
+        let mouse_pos = mouse_position();
+        self.events.hover(self.get_bounds().contains(Vec2::new(mouse_pos.0, mouse_pos.1)));
+
         // Handle scrolling with mouse wheel
         self.handle_scroll();
         
@@ -400,12 +586,24 @@ impl ListView {
                 }
             }
         }
+
+        self.notify_select(old_index);
     }
 
     // Method to draw the list view
     pub fn draw(&mut self) {
+        // Only draw if the list is visible
+        if !self.visible {
+            self.events.hover(false);
+            return;
+        }
+
         // Handle all updates first (previously in the update method)
-        self.update();
+        if self.enabled {
+            self.update();
+        } else {
+            self.events.hover(false);
+        }
         
         let item_height = self.font_size as f32 * self.item_spacing;
         let (width, height) = self.calculate_dimensions();
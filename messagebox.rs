@@ -5,6 +5,7 @@ Adds a message box (dialog) component for displaying messages and options to use
 
 In your ui.rs file in the root, add the following to the end of the file
     pub mod messagebox;
+    pub mod theme;
     
 For info boxs add the following with the use commands:
 use crate::ui::messagebox::MessageBox;
@@ -152,6 +153,8 @@ IMPORTANT NOTES:
 */
 
 use macroquad::prelude::*;
+
+use crate::ui::theme::{Theme, current_theme};
 #[cfg(feature = "scale")]
 use crate::modules::scale::mouse_position_world as mouse_position;
 
@@ -215,6 +218,7 @@ impl MessageBox {
         } else {
             None
         };
+        let theme = current_theme();
         
         Self {
             visible: false,
@@ -229,14 +233,14 @@ impl MessageBox {
             height,
             title_height: 30.0,
             button_height: 40.0,
-            padding: 15.0,
-            title_bg_color: DARKBLUE,
-            bg_color: Color::new(0.9, 0.9, 0.9, 1.0), // Light gray
-            title_text_color: WHITE,
-            message_text_color: BLACK,
-            button_bg_color: LIGHTGRAY,
-            button_hover_color: SKYBLUE, // Default hover color for better visibility
-            button_text_color: BLACK,
+            padding: theme.padding,
+            title_bg_color: theme.accent,
+            bg_color: theme.background,
+            title_text_color: theme.text_on_accent,
+            message_text_color: theme.text,
+            button_bg_color: theme.surface,
+            button_hover_color: theme.highlight, // Hover color for better visibility
+            button_text_color: theme.text,
             close_button_size: 20.0,
             show_close_button: true,
             modal: true,
@@ -245,10 +249,10 @@ impl MessageBox {
             dragging: false,
             drag_offset_x: 0.0,
             drag_offset_y: 0.0,
-            font: None,
-            title_font_size: 18.0,
-            message_font_size: 16.0,
-            button_font_size: 16.0,
+            font: theme.font.clone(),
+            title_font_size: theme.title_font_size as f32,
+            message_font_size: theme.font_size as f32,
+            button_font_size: theme.font_size as f32,
         }
     }
     
@@ -267,6 +271,18 @@ impl MessageBox {
         self
     }
     
+    // Get dialog position
+    #[allow(unused)]
+    pub fn get_position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+    
+    // Get dialog size
+    #[allow(unused)]
+    pub fn get_size(&self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
+    
     // Set dialog size
     #[allow(unused)]
     pub fn set_size(&mut self, width: f32, height: f32) -> &mut Self {
@@ -293,6 +309,20 @@ impl MessageBox {
         self
     }
     
+    // Restyle the dialog with a theme (keeps the modal overlay color and font sizes)
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.title_bg_color = theme.accent;
+        self.bg_color = theme.background;
+        self.title_text_color = theme.text_on_accent;
+        self.message_text_color = theme.text;
+        self.padding = theme.padding;
+        if let Some(font) = &theme.font {
+            self.font = Some(font.clone());
+        }
+        self.with_button_colors(theme.surface, theme.highlight, theme.text)
+    }
+    
     // Customize button colors
     #[allow(unused)]
     pub fn with_button_colors(
@@ -6,6 +6,7 @@ Program Details: Adds a progress bar component for displaying completion status
 To import you need:
 In your ui.rs file in the root, add the following to the end of the file
     pub mod progressbar;
+    pub mod theme;
     
 Then add the following with the use commands:
 use crate::ui::progressbar::ProgressBar;
@@ -54,6 +55,8 @@ Then inside the loop you would use:
 
 use macroquad::prelude::*;
 
+use crate::ui::theme::{Theme, current_theme};
+
 #[derive(PartialEq, Clone, Copy)]
 #[allow(dead_code)]
 pub enum ProgressBarOrientation {
@@ -84,11 +87,13 @@ pub struct ProgressBar {
     animate: bool,
     animation_speed: f32,
     completed_callback: Option<Box<dyn Fn()>>,
+    visible: bool,
 }
 
 impl ProgressBar {
     // Create a new horizontal progress bar
     pub fn new(x: f32, y: f32, width: f32, height: f32, min_value: f32, max_value: f32, initial_value: f32) -> Self {
+        let theme = current_theme();
         Self {
             x,
             y,
@@ -99,18 +104,19 @@ impl ProgressBar {
             current_value: initial_value.clamp(min_value, max_value),
             target_value: initial_value.clamp(min_value, max_value),
             orientation: ProgressBarOrientation::Horizontal,
-            background_color: GRAY,
-            fill_color: GREEN,
-            text_color: WHITE,
+            background_color: theme.track,
+            fill_color: theme.fill,
+            text_color: theme.text_on_accent,
             show_percentage: false,
             show_value: false,
             label: None,
             border: false,
-            border_color: DARKGRAY,
+            border_color: theme.outline,
             border_thickness: 2.0,
             animate: false,
             animation_speed: 5.0,
             completed_callback: None,
+            visible: true,
         }
     }
     
@@ -130,6 +136,13 @@ impl ProgressBar {
         self
     }
     
+    // Restyle the progress bar with a theme
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.border_color = theme.outline;
+        self.with_colors(theme.track, theme.fill, theme.text_on_accent)
+    }
+    
     // Set a label for the progress bar
     #[allow(dead_code)]
     pub fn with_label(&mut self, label: impl Into<String>) -> &mut Self {
@@ -175,6 +188,44 @@ impl ProgressBar {
         self
     }
     
+    // Getter for x position
+    #[allow(dead_code)]
+    pub fn get_x(&self) -> f32 {
+        self.x
+    }
+
+    // Getter for y position
+    #[allow(dead_code)]
+    pub fn get_y(&self) -> f32 {
+        self.y
+    }
+
+    // Setter for position
+    #[allow(dead_code)]
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    // Getter for the bar rectangle
+    #[allow(dead_code)]
+    pub fn get_bounds(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+
+    // Visibility getter and setter
+    #[allow(dead_code)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(dead_code)]
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        self
+    }
+    
     // Get current progress value
     #[allow(dead_code)]
     pub fn value(&self) -> f32 {
@@ -258,6 +309,11 @@ impl ProgressBar {
         // Update animation state
         self.update();
         
+        // Only draw if the progress bar is visible
+        if !self.visible {
+            return;
+        }
+        
         // Draw background
         if self.border {
             // Draw border
@@ -0,0 +1,440 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Save slots for games, built on TextFile. Each slot keeps the game data
+together with information about the save (when it was made, play time, a thumbnail
+and the game version), and there are rotating autosaves.
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod saves;
+
+This uses serde and serde_json. In the terminal run:
+    cargo add serde@1.0 --features derive
+    cargo add serde_json@1.0
+
+Then add the following with the use commands:
+    use crate::utils::saves::SaveManager;
+    use crate::utils::textfiles::Migrations;     // Only if you use with_migrations
+    use serde::{Deserialize, Serialize};
+
+Your game data has to be a struct that serde can save:
+    #[derive(Serialize, Deserialize, Default)]
+    #[serde(default)]
+    struct GameState {
+        level: u32,
+        score: i32,
+    }
+
+Then above the loop you would use:
+    let mut saves = SaveManager::new("saves");   // Folder (or web storage prefix) for the slots
+    saves.with_version(1)                        // Optional: your game's save version
+         .with_autosave(120.0, 3);               // Optional: autosave every 2 minutes into 3 rotating slots
+
+When GameState changes between versions, give the manager the steps to upgrade old saves
+(see Migrations in textfiles.rs). This also sets the version new saves are written as:
+    let mut migrations = Migrations::new(2);
+    migrations.add(1, |mut data| {               // Version 1 -> 2: lives was added
+        data["lives"] = serde_json::json!(3);
+        Ok(data)
+    });
+    saves.with_migrations(migrations);
+Loading a slot saved by an older version then upgrades it before turning it into GameState.
+
+Saving and loading (slots are named, SaveManager::slot(1) gives "slot1" for numbered slots):
+    // Save
+    if let Err(e) = saves.save(&SaveManager::slot(1), &state).await {
+        println!("Error saving: {}", e);
+    }
+
+    // Save with a label and a thumbnail image path
+    saves.save_with(&SaveManager::slot(1), &state, "Castle - Level 3", Some("saves/slot1.png")).await.ok();
+
+    // Load
+    match saves.load::<GameState>(&SaveManager::slot(1)).await {
+        Ok(loaded) => state = loaded,
+        Err(e) => println!("Error loading: {}", e),
+    }
+
+Inside the loop, keep the play time counting and autosave when it is time:
+    saves.update();
+    if saves.autosave_due() {
+        saves.autosave(&state).await.ok();
+    }
+
+Listing slots for a load menu (newest first, autosaves included):
+    for info in saves.list().await.unwrap_or_default() {
+        println!("{} {} played {}", info.slot, info.label, info.play_time_text());
+    }
+
+    // The most recent save of any kind, for a "Continue" button
+    if let Ok(Some(info)) = saves.latest().await {
+        state = saves.load(&info.slot).await.unwrap_or_default();
+    }
+
+Other options:
+    saves.delete("slot1").await.ok();          // Delete a slot
+    saves.exists("slot1").await;               // Is there a save in the slot
+    saves.info("slot1").await;                 // Just the information, without the game data
+    saves.play_time();                         // Seconds played (carried over when a slot is loaded)
+    saves.set_play_time(0.0);                  // Reset when starting a new game
+
+Note: On desktop every write goes to a temp file first and is then renamed over the old
+one, so a crash while saving never corrupts the player's save.
+*/
+
+use macroquad::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::textfiles::{Migrations, TextFile};
+
+// Information stored with every save
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveInfo {
+    pub slot: String,
+    pub label: String,
+    pub saved_at: f64,             // Seconds since Jan 1 1970
+    pub play_time: f64,            // Seconds played when the save was made
+    pub thumbnail: Option<String>, // Path to a screenshot or picture for the slot
+    pub version: u32,              // Save version from SaveManager::with_version
+    pub autosave: bool,
+}
+
+#[allow(unused)]
+impl SaveInfo {
+    // Play time as "H:MM:SS" (or "M:SS" under an hour)
+    pub fn play_time_text(&self) -> String {
+        let total = self.play_time.max(0.0) as u64;
+        let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+
+    // How many seconds ago the save was made
+    pub fn age(&self) -> f64 {
+        (macroquad::miniquad::date::now() - self.saved_at).max(0.0)
+    }
+}
+
+// What a slot looks like in storage
+#[derive(Serialize)]
+struct SlotFile<T> {
+    info: SaveInfo,
+    data: T,
+}
+
+// Reading just the information of a slot skips the game data
+#[derive(Deserialize)]
+struct SlotInfoOnly {
+    info: SaveInfo,
+}
+
+// A slot with its game data still as JSON, so it can be upgraded before it is read
+#[derive(Deserialize)]
+struct RawSlotFile {
+    info: SaveInfo,
+    data: serde_json::Value,
+}
+
+pub struct SaveManager {
+    folder: String,
+    version: u32,
+    migrations: Option<Migrations>,
+    play_time: f64,
+    autosave_interval: Option<f32>,
+    autosave_slots: usize,
+    autosave_timer: f32,
+}
+
+#[allow(unused)]
+impl SaveManager {
+    // "" (or "/") keeps the slot files next to the game instead of in a folder
+    pub fn new(folder: &str) -> Self {
+        Self {
+            folder: folder.trim_end_matches('/').to_string(),
+            version: 1,
+            migrations: None,
+            play_time: 0.0,
+            autosave_interval: None,
+            autosave_slots: 3,
+            autosave_timer: 0.0,
+        }
+    }
+
+    // Name of a numbered slot ("slot1", "slot2", ...)
+    pub fn slot(number: usize) -> String {
+        format!("slot{}", number)
+    }
+
+    // Version written into every save
+    pub fn with_version(&mut self, version: u32) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    // Upgrade saves from older versions when they are loaded
+    // New saves are written as migrations.current()
+    pub fn with_migrations(&mut self, migrations: Migrations) -> &mut Self {
+        self.version = migrations.current();
+        self.migrations = Some(migrations);
+        self
+    }
+
+    // Autosave every `interval` seconds, rotating through `slots` autosave slots
+    pub fn with_autosave(&mut self, interval: f32, slots: usize) -> &mut Self {
+        self.autosave_interval = Some(interval.max(0.0));
+        self.autosave_slots = slots.max(1);
+        self.autosave_timer = 0.0;
+        self
+    }
+
+    pub fn play_time(&self) -> f64 {
+        self.play_time
+    }
+
+    pub fn set_play_time(&mut self, seconds: f64) -> &mut Self {
+        self.play_time = seconds.max(0.0);
+        self
+    }
+
+    // Call once per frame to count play time and the autosave timer
+    pub fn update(&mut self) {
+        let delta = get_frame_time();
+        self.play_time += delta as f64;
+        if self.autosave_interval.is_some() {
+            self.autosave_timer += delta;
+        }
+    }
+
+    // True when autosave is turned on and the interval has passed since the last save
+    pub fn autosave_due(&self) -> bool {
+        self.autosave_interval.is_some_and(|interval| self.autosave_timer >= interval)
+    }
+
+    // Save game data into a slot
+    pub async fn save<T: Serialize>(&mut self, slot: &str, data: &T) -> Result<SaveInfo, String> {
+        self.save_with(slot, data, "", None).await
+    }
+
+    // Save game data into a slot with a label and thumbnail
+    pub async fn save_with<T: Serialize>(
+        &mut self,
+        slot: &str,
+        data: &T,
+        label: &str,
+        thumbnail: Option<&str>,
+    ) -> Result<SaveInfo, String> {
+        self.write_slot(slot, data, label, thumbnail, false).await
+    }
+
+    // Save into the oldest (or first empty) autosave slot
+    pub async fn autosave<T: Serialize>(&mut self, data: &T) -> Result<SaveInfo, String> {
+        let mut target = None;
+        let mut oldest = f64::MAX;
+        for number in 1..=self.autosave_slots {
+            let slot = format!("autosave{}", number);
+            match self.info(&slot).await {
+                Ok(Some(info)) if info.saved_at < oldest => {
+                    oldest = info.saved_at;
+                    target = Some(slot);
+                }
+                Ok(Some(_)) => {}
+                // An empty slot, or one that can't be read, is written over first
+                Ok(None) | Err(_) => {
+                    target = Some(slot);
+                    break;
+                }
+            }
+        }
+
+        let slot = target.unwrap_or_else(|| "autosave1".to_string());
+        self.write_slot(&slot, data, "Autosave", None, true).await
+    }
+
+    // Load game data from a slot, play time continues from the save
+    // Saves from older versions are upgraded first if with_migrations was used
+    pub async fn load<T: DeserializeOwned>(&mut self, slot: &str) -> Result<T, String> {
+        let file: RawSlotFile = TextFile::load_json(&self.slot_path(slot)?).await?;
+
+        let data = match &self.migrations {
+            Some(migrations) => migrations
+                .migrate(file.data, file.info.version)
+                .map_err(|e| format!("Failed to upgrade slot {}: {}", slot, e))?,
+            None => file.data,
+        };
+        let data = serde_json::from_value(data)
+            .map_err(|e| format!("Failed to read slot {}: {}", slot, e))?;
+
+        self.play_time = file.info.play_time;
+        self.autosave_timer = 0.0;
+        Ok(data)
+    }
+
+    // Information about a slot without loading its game data (None if the slot is empty)
+    pub async fn info(&self, slot: &str) -> Result<Option<SaveInfo>, String> {
+        let path = self.slot_path(slot)?;
+        if !TextFile::exists(&path).await? {
+            return Ok(None);
+        }
+        let file: SlotInfoOnly = TextFile::load_json(&path).await?;
+        Ok(Some(file.info))
+    }
+
+    pub async fn exists(&self, slot: &str) -> bool {
+        match self.slot_path(slot) {
+            Ok(path) => TextFile::exists(&path).await.unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    // Information about every saved slot, newest first
+    // Slots that can't be read are skipped
+    pub async fn list(&self) -> Result<Vec<SaveInfo>, String> {
+        let mut list = Vec::new();
+        for slot in self.load_index().await? {
+            if let Ok(Some(info)) = self.info(&slot).await {
+                list.push(info);
+            }
+        }
+        list.sort_by(|a, b| b.saved_at.total_cmp(&a.saved_at));
+        Ok(list)
+    }
+
+    // The most recent save, for a "Continue" button
+    pub async fn latest(&self) -> Result<Option<SaveInfo>, String> {
+        Ok(self.list().await?.into_iter().next())
+    }
+
+    // Delete a slot (not an error if it is already empty)
+    pub async fn delete(&mut self, slot: &str) -> Result<(), String> {
+        let path = self.slot_path(slot)?;
+
+        // Take it out of the index first so a crash never leaves a listed slot with no file
+        let mut index = self.load_index().await?;
+        if index.iter().any(|name| name == slot) {
+            index.retain(|name| name != slot);
+            TextFile::save_json(&self.index_path(), &index).await?;
+        }
+        TextFile::remove(&path).await
+    }
+
+    async fn write_slot<T: Serialize>(
+        &mut self,
+        slot: &str,
+        data: &T,
+        label: &str,
+        thumbnail: Option<&str>,
+        autosave: bool,
+    ) -> Result<SaveInfo, String> {
+        let path = self.slot_path(slot)?;
+        let info = SaveInfo {
+            slot: slot.to_string(),
+            label: label.to_string(),
+            saved_at: macroquad::miniquad::date::now(),
+            play_time: self.play_time,
+            thumbnail: thumbnail.map(|path| path.to_string()),
+            version: self.version,
+            autosave,
+        };
+
+        TextFile::save_json(&path, &SlotFile { info: info.clone(), data }).await?;
+
+        // Add the slot to the index after its file is safely written
+        let mut index = self.load_index().await?;
+        if !index.iter().any(|name| name == slot) {
+            index.push(slot.to_string());
+            TextFile::save_json(&self.index_path(), &index).await?;
+        }
+
+        self.autosave_timer = 0.0;
+        Ok(info)
+    }
+
+    // Web storage can't list its keys, so the slot names are kept in an index
+    async fn load_index(&self) -> Result<Vec<String>, String> {
+        TextFile::load_json_or_default(&self.index_path()).await
+    }
+
+    fn index_path(&self) -> String {
+        self.file_path("slots")
+    }
+
+    // Files go in the folder, or next to the game when the folder is ""
+    fn file_path(&self, name: &str) -> String {
+        if self.folder.is_empty() {
+            format!("{}.json", name)
+        } else {
+            format!("{}/{}.json", self.folder, name)
+        }
+    }
+
+    fn slot_path(&self, slot: &str) -> Result<String, String> {
+        let valid = !slot.is_empty()
+            && slot != "slots"
+            && slot.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == ' ');
+        if !valid {
+            return Err(format!("Invalid slot name: {}", slot));
+        }
+        Ok(self.file_path(slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::textfiles::test_helpers::{block_on, memory};
+    use crate::utils::textfiles::StorageBackend;
+
+    #[test]
+    fn autosave_writes_over_a_broken_slot() {
+        let storage = memory();
+        let mut saves = SaveManager::new("saves");
+        saves.with_autosave(60.0, 2);
+        block_on(saves.autosave(&1)).unwrap();
+        storage.write("saves/autosave2.json", "not json").unwrap();
+
+        let info = block_on(saves.autosave(&2)).unwrap();
+        assert_eq!(info.slot, "autosave2");
+        assert_eq!(block_on(saves.load::<i32>("autosave2")).unwrap(), 2);
+        // Both slots can be read again, so the oldest is next
+        assert_eq!(block_on(saves.autosave(&3)).unwrap().slot, "autosave1");
+    }
+
+    #[test]
+    fn empty_folder_keeps_files_next_to_the_game() {
+        let storage = memory();
+        for folder in ["", "/"] {
+            let mut saves = SaveManager::new(folder);
+            block_on(saves.save("slot1", &5)).unwrap();
+            assert_eq!(block_on(saves.load::<i32>("slot1")).unwrap(), 5);
+        }
+        assert_eq!(storage.names(), vec!["slot1.json", "slots.json"]);
+    }
+
+    #[test]
+    fn with_migrations_upgrades_an_old_slot() {
+        let storage = memory();
+        // A slot written by version 1 of the game, before lives was added
+        storage
+            .write("saves/slot1.json", r#"{ "info": { "slot": "slot1", "version": 1, "play_time": 30.0 }, "data": { "level": 4 } }"#)
+            .unwrap();
+        let mut migrations = Migrations::new(2);
+        migrations.add(1, |mut data| {
+            data["lives"] = serde_json::json!(3);
+            Ok(data)
+        });
+        let mut saves = SaveManager::new("saves");
+        saves.with_migrations(migrations);
+
+        let data: serde_json::Value = block_on(saves.load("slot1")).unwrap();
+        assert_eq!(data, serde_json::json!({ "level": 4, "lives": 3 }));
+        assert_eq!(saves.play_time(), 30.0);
+        assert_eq!(block_on(saves.save("slot1", &data)).unwrap().version, 2);
+    }
+}
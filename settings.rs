@@ -0,0 +1,296 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: A settings store (volume, key bindings, fullscreen, ...) that is saved
+with TextFile, so settings are kept between runs on both desktop and web
+
+To import you need:
+In your utils.rs file located in the root folder add the following to the end of the file:
+    pub mod textfiles;
+    pub mod settings;
+
+This uses serde and serde_json. In the terminal run:
+    cargo add serde@1.0 --features derive
+    cargo add serde_json@1.0
+
+Then add the following with the use commands:
+    use crate::utils::settings::Settings;
+
+Then above the loop you would use:
+    let settings = Settings::load("settings.json").await;
+
+    // Read settings, the second value is used if the setting hasn't been saved yet
+    let volume = settings.get_f32("volume", 0.8);
+    let fullscreen = settings.get_bool("fullscreen", false);
+    let player = settings.get_string("player_name", "Player 1");
+    let jump_key = settings.get_string("key_jump", "Space");
+
+    // Change settings (any type serde can save works)
+    settings.set("fullscreen", true);
+    settings.set("key_jump", "W");
+
+    // Keep a Slider and a setting in sync, moving the slider saves the setting
+    volume_slider.set_value(settings.get_f32("volume", 0.8));
+    volume_slider.on_change(settings.setter_f32("volume"));
+
+    // Run code when a setting changes
+    settings.on_change("volume", |value| println!("Volume is now {}", value));
+    settings.on_any_change(|key, value| println!("{} is now {}", key, value));
+
+Then inside the loop:
+    settings.update();   // Saves changes once nothing has changed for a moment
+
+Changes are not written every frame while a slider is dragged. They are saved once
+nothing has changed for the flush delay (1 second by default), when flush() is called,
+or when the last copy of the Settings is dropped.
+
+Other options:
+    settings.with_flush_delay(0.5);          // Seconds to wait before saving changes
+    settings.flush();                        // Save right now
+    settings.contains("volume");             // Has the setting been set
+    settings.remove("volume");               // Go back to the default value
+    settings.reset();                        // Go back to defaults for everything
+    settings.last_error();                   // The last error from saving, if any
+
+Note: Settings is cheap to clone, every clone shares the same settings. That is how
+setter_f32 can change them from inside a Slider's on_change.
+*/
+
+use macroquad::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::utils::textfiles::TextFile;
+
+// Shorter names for the listener types
+type KeyListener = Rc<dyn Fn(&Value)>;
+type AnyListener = Rc<dyn Fn(&str, &Value)>;
+
+struct SettingsData {
+    path: String,
+    values: BTreeMap<String, Value>,
+    dirty: bool,
+    flush_delay: f32,
+    since_change: f32,
+    last_error: Option<String>,
+    listeners: Vec<(String, KeyListener)>,
+    any_listeners: Vec<AnyListener>,
+}
+
+impl SettingsData {
+    fn flush(&mut self) -> Result<(), String> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let json = serde_json::to_string_pretty(&self.values)
+            .map_err(|e| format!("Failed to convert settings to JSON: {}", e))?;
+        let result = TextFile::backend().write(&self.path, &json);
+        match &result {
+            Ok(()) => {
+                self.dirty = false;
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e.clone()),
+        }
+        result
+    }
+}
+
+// Save anything that hasn't been saved when the last copy goes away
+impl Drop for SettingsData {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[derive(Clone)]
+pub struct Settings {
+    data: Rc<RefCell<SettingsData>>,
+}
+
+#[allow(unused)]
+impl Settings {
+    // Settings with nothing loaded, saved to `path` when they change
+    pub fn new(path: &str) -> Self {
+        Self {
+            data: Rc::new(RefCell::new(SettingsData {
+                path: path.to_string(),
+                values: BTreeMap::new(),
+                dirty: false,
+                flush_delay: 1.0,
+                since_change: 0.0,
+                last_error: None,
+                listeners: Vec::new(),
+                any_listeners: Vec::new(),
+            })),
+        }
+    }
+
+    // Load settings saved at `path`
+    // If there are none yet (or the file is broken) every setting starts at its default
+    pub async fn load(path: &str) -> Self {
+        let settings = Self::new(path);
+        match TextFile::load_json_or_default::<BTreeMap<String, Value>>(path).await {
+            Ok(values) => settings.data.borrow_mut().values = values,
+            Err(e) => settings.data.borrow_mut().last_error = Some(e),
+        }
+        settings
+    }
+
+    // Seconds to wait after the last change before saving
+    pub fn with_flush_delay(&self, seconds: f32) -> &Self {
+        self.data.borrow_mut().flush_delay = seconds.max(0.0);
+        self
+    }
+
+    // Read a setting as any type serde can load, or `default` if it isn't set
+    // (or was saved as a different type)
+    pub fn get<T: DeserializeOwned>(&self, key: &str, default: T) -> T {
+        self.data
+            .borrow()
+            .values
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or(default)
+    }
+
+    pub fn get_f32(&self, key: &str, default: f32) -> f32 {
+        self.get(key, default)
+    }
+
+    pub fn get_i32(&self, key: &str, default: i32) -> i32 {
+        self.get(key, default)
+    }
+
+    pub fn get_bool(&self, key: &str, default: bool) -> bool {
+        self.get(key, default)
+    }
+
+    pub fn get_string(&self, key: &str, default: &str) -> String {
+        self.get(key, default.to_string())
+    }
+
+    // Change a setting, listeners are told if the value is different
+    pub fn set<T: Serialize>(&self, key: &str, value: T) {
+        let Ok(value) = serde_json::to_value(value) else {
+            return;
+        };
+
+        {
+            let mut data = self.data.borrow_mut();
+            if data.values.get(key) == Some(&value) {
+                return;
+            }
+            data.values.insert(key.to_string(), value.clone());
+            data.dirty = true;
+            data.since_change = 0.0;
+        }
+
+        self.notify(key, &value);
+    }
+
+    // A closure that sets `key`, made for Slider::on_change
+    pub fn setter_f32(&self, key: &str) -> impl Fn(f32) + 'static {
+        let settings = self.clone();
+        let key = key.to_string();
+        move |value| settings.set(&key, value)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.data.borrow().values.contains_key(key)
+    }
+
+    // Remove a setting so its default is used again
+    pub fn remove(&self, key: &str) {
+        let removed = {
+            let mut data = self.data.borrow_mut();
+            let removed = data.values.remove(key).is_some();
+            if removed {
+                data.dirty = true;
+                data.since_change = 0.0;
+            }
+            removed
+        };
+
+        if removed {
+            self.notify(key, &Value::Null);
+        }
+    }
+
+    // Remove every setting
+    pub fn reset(&self) {
+        let keys: Vec<String> = self.data.borrow().values.keys().cloned().collect();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    // Run `callback` with the new value whenever `key` changes
+    // (the value is null when the setting is removed)
+    pub fn on_change<F: Fn(&Value) + 'static>(&self, key: &str, callback: F) -> &Self {
+        self.data.borrow_mut().listeners.push((key.to_string(), Rc::new(callback)));
+        self
+    }
+
+    // Run `callback` with the key and new value whenever any setting changes
+    pub fn on_any_change<F: Fn(&str, &Value) + 'static>(&self, callback: F) -> &Self {
+        self.data.borrow_mut().any_listeners.push(Rc::new(callback));
+        self
+    }
+
+    // Call once per frame, saves changes once nothing has changed for the flush delay
+    pub fn update(&self) {
+        let mut data = self.data.borrow_mut();
+        if !data.dirty {
+            return;
+        }
+
+        data.since_change += get_frame_time();
+        if data.since_change >= data.flush_delay {
+            // On an error wait another flush delay before trying again
+            data.since_change = 0.0;
+            let _ = data.flush();
+        }
+    }
+
+    // Save changes right now
+    pub fn flush(&self) -> Result<(), String> {
+        self.data.borrow_mut().flush()
+    }
+
+    // True if there are changes that haven't been saved yet
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.data.borrow().dirty
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.data.borrow().last_error.clone()
+    }
+
+    // Listeners are copied out first so they can read or change settings themselves
+    fn notify(&self, key: &str, value: &Value) {
+        let (listeners, any_listeners): (Vec<KeyListener>, Vec<AnyListener>) = {
+            let data = self.data.borrow();
+            (
+                data.listeners
+                    .iter()
+                    .filter(|(listen_key, _)| listen_key == key)
+                    .map(|(_, listener)| listener.clone())
+                    .collect(),
+                data.any_listeners.clone(),
+            )
+        };
+
+        for listener in listeners {
+            listener(value);
+        }
+        for listener in any_listeners {
+            listener(key, value);
+        }
+    }
+}
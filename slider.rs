@@ -7,6 +7,8 @@ Adds a slider UI component for numeric input
 
 In your ui.rs file in the root, add the following to the end of the file
     pub mod slider;
+    pub mod theme;
+    pub mod events;
     
 Then add the following with the use commands:
 use crate::ui::slider::Slider;
@@ -47,11 +49,27 @@ Then inside the loop you would use:
     // Get the current value
     let volume = volume_slider.value();
     
+    // Nudge the value like an arrow key press (used by the focus manager)
+    volume_slider.step(1.0);
+    volume_slider.with_keyboard_step(5.0);  // Size of one step (default is 1/20 of the range)
+
+    // Run code when the user moves the slider (needs pub mod events; in ui.rs)
+    volume_slider.on_change(|value| println!("Volume: {}", value));
+    volume_slider.with_event_queue(&events, "volume");  // Or send events to a queue
+
+    // Move, hide or disable the slider
+    volume_slider.set_position(120.0, 220.0);
+    volume_slider.set_visible(false);
+    volume_slider.set_enabled(false);
+
     // You can use the value in your application
     play_sound(sound, PlaySoundParams { volume: volume / 100.0, looped: true });
 */
 
 use macroquad::prelude::*;
+
+use crate::ui::events::{EventHandlers, EventQueue};
+use crate::ui::theme::{Theme, current_theme};
 #[cfg(feature = "scale")]
 use crate::modules::scale::mouse_position_world as mouse_position;
 
@@ -79,11 +97,16 @@ pub struct Slider {
     show_value: bool,
     value_precision: usize,
     label: Option<String>,
+    visible: bool,
+    enabled: bool,
+    keyboard_step: f32,
+    events: EventHandlers,
 }
 
 impl Slider {
     // Create a new horizontal slider
     pub fn new(x: f32, y: f32, width: f32, height: f32, min_value: f32, max_value: f32, initial_value: f32) -> Self {
+        let theme = current_theme();
         Self {
             x,
             y,
@@ -93,15 +116,19 @@ impl Slider {
             max_value,
             current_value: initial_value.clamp(min_value, max_value),
             orientation: SliderOrientation::Horizontal,
-            track_color: GRAY,
-            handle_color: DARKGRAY,
-            handle_hover_color: BLUE,
-            label_color: WHITE,
+            track_color: theme.track,
+            handle_color: theme.outline,
+            handle_hover_color: theme.hover,
+            label_color: theme.text_on_accent,
             handle_radius: height / 2.0,
             dragging: false,
             show_value: true,
             value_precision: 1,
             label: None,
+            visible: true,
+            enabled: true,
+            keyboard_step: (max_value - min_value) / 20.0,
+            events: EventHandlers::new(),
         }
     }
     
@@ -123,6 +150,12 @@ impl Slider {
         self
     }
     
+    // Restyle the slider with a theme
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.with_colors(theme.track, theme.outline, theme.text_on_accent, theme.hover)
+    }
+    
     // Set a label for the slider
     #[allow(unused)]
     pub fn with_label(&mut self, label: impl Into<String>) -> &mut Self {
@@ -156,6 +189,123 @@ impl Slider {
         self.current_value = value.clamp(self.min_value, self.max_value);
     }
     
+    // Set how far one arrow key press moves the slider
+    #[allow(unused)]
+    pub fn with_keyboard_step(&mut self, step: f32) -> &mut Self {
+        self.keyboard_step = step.abs();
+        self
+    }
+    
+    // Move the value by a number of keyboard steps (negative moves down)
+    #[allow(unused)]
+    pub fn step(&mut self, steps: f32) {
+        if self.enabled {
+            let old_value = self.current_value;
+            self.set_value(self.current_value + steps * self.keyboard_step);
+            if self.current_value != old_value {
+                self.events.value_changed(self.current_value);
+            }
+        }
+    }
+    
+    // Run a function when the user changes the value
+    #[allow(unused)]
+    pub fn on_change<F: Fn(f32) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_value_change(callback);
+        self
+    }
+    
+    // Run a function when the mouse moves onto the slider
+    #[allow(unused)]
+    pub fn on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_enter(callback);
+        self
+    }
+    
+    // Run a function when the mouse moves off the slider
+    #[allow(unused)]
+    pub fn on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_leave(callback);
+        self
+    }
+    
+    // Run a function when the slider gains or loses keyboard focus
+    #[allow(unused)]
+    pub fn on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_focus(callback);
+        self
+    }
+    
+    // Send this slider's events to a queue under the given id
+    #[allow(unused)]
+    pub fn with_event_queue(&mut self, queue: &EventQueue, id: &str) -> &mut Self {
+        self.events.set_queue(queue, id);
+        self
+    }
+    
+    // Called by the focus manager when the slider gains or loses focus
+    #[allow(unused)]
+    pub fn set_focused(&mut self, focused: bool) -> &mut Self {
+        self.events.focus(focused);
+        self
+    }
+    
+    // Getter for x position
+    #[allow(unused)]
+    pub fn get_x(&self) -> f32 {
+        self.x
+    }
+
+    // Getter for y position
+    #[allow(unused)]
+    pub fn get_y(&self) -> f32 {
+        self.y
+    }
+
+    // Setter for position
+    #[allow(unused)]
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    // Getter for the track rectangle (label and value text are drawn outside it)
+    #[allow(unused)]
+    pub fn get_bounds(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+
+    // Visibility getter and setter
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(unused)]
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        if !visible {
+            self.dragging = false;
+        }
+        self
+    }
+
+    // Enabled getter and setter (a disabled slider is drawn dimmed and ignores the mouse)
+    #[allow(unused)]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[allow(unused)]
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        if !enabled {
+            self.dragging = false;
+        }
+        self
+    }
+
     // Calculate handle position based on current value
     fn handle_position(&self) -> Vec2 {
         match self.orientation {
@@ -200,7 +350,15 @@ impl Slider {
     
     // Update slider state
     pub fn update(&mut self) {
+        // Hidden or disabled sliders don't react to the mouse
+        if !self.visible || !self.enabled {
+            self.events.hover(false);
+            return;
+        }
+
         let mouse_pos = Vec2::new(mouse_position().0, mouse_position().1);
+        let old_value = self.current_value;
+        self.events.hover(self.get_bounds().contains(mouse_pos) || self.is_mouse_over_handle());
         
         // Check for initial click
         if is_mouse_button_pressed(MouseButton::Left) && self.is_mouse_over_handle() {
@@ -240,10 +398,19 @@ impl Slider {
                 self.dragging = true;
             }
         }
+        
+        if self.current_value != old_value {
+            self.events.value_changed(self.current_value);
+        }
     }
     
     // Draw slider
     pub fn draw(&self) {
+        // Only draw if the slider is visible
+        if !self.visible {
+            return;
+        }
+
         // Draw track
         match self.orientation {
             SliderOrientation::Horizontal => {
@@ -290,7 +457,9 @@ impl Slider {
         
         // Draw handle
         let handle_pos = self.handle_position();
-        let handle_color = if self.is_mouse_over_handle() || self.dragging { 
+        let handle_color = if !self.enabled {
+            Color::new(self.handle_color.r, self.handle_color.g, self.handle_color.b, 0.5)
+        } else if self.is_mouse_over_handle() || self.dragging { 
             self.handle_hover_color 
         } else { 
             self.handle_color 
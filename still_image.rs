@@ -77,6 +77,7 @@ Additional functionality:
 - Zoom controls: set_zoom(), zoom_in(), zoom_out(), reset_zoom()
 - Stretch controls: enable_stretch(), disable_stretch(), toggle_stretch()
 - Position control: set_position()
+- Visibility control: set_visible(), is_visible()
 - Check if empty: is_empty()
 */
 use macroquad::prelude::*;
@@ -93,6 +94,7 @@ pub struct StillImage {
     zoom_level: f32, // Zoom factor to scale the image
     filename: String, // Store the original filename/path
     angle: f32, // Angle of rotation
+    visible: bool, // Whether the image should be drawn
 }
 
 impl StillImage {
@@ -119,6 +121,7 @@ impl StillImage {
             zoom_level: zoom_level.max(0.1),
             filename,
             angle: 0.0,
+            visible: true,
         }
     }
 
@@ -151,6 +154,7 @@ impl StillImage {
                 zoom_level: zoom_level.max(0.1), // Ensure minimum zoom
                 filename: "__empty__".to_string(), // Use a special filename
                 angle: 0.0, // Default angle
+                visible: true,
             };
         }
         
@@ -167,11 +171,17 @@ impl StillImage {
             zoom_level: zoom_level.max(0.1), // Ensure minimum zoom
             filename: asset_path.to_string(), // Store the original filename
             angle: 0.0, // Default angle
+            visible: true,
         }
     }
 
     // Method to draw the image with current settings
     pub fn draw(&self) {
+        // Only draw if the image is visible
+        if !self.visible {
+            return;
+        }
+
         // Get the size to use for drawing
         let (draw_width, draw_height) = if self.stretch_enabled {
             (self.width, self.height)
@@ -251,6 +261,17 @@ impl StillImage {
         self.y = y;
     }
 
+    // Visibility getter and setter
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(unused)]
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
     // Get the original filename/path of the loaded image
     #[allow(unused)]
     pub fn get_filename(&self) -> &str {
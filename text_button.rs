@@ -7,6 +7,8 @@ Adds a button object
 
 In your ui.rs file add the following to the end of the file:
     pub mod text_button;
+    pub mod theme;
+    pub mod events;
 
 Then with the other use commands add:
 use crate::ui::text_button::TextButton;
@@ -67,16 +69,34 @@ Where the first two values are x and y positions, and the optional values are fo
 To change the button's text:
     btn_text.set_text("New Text");
 
+To use the colors, font and font size from the current theme (see theme.rs):
+    let btn_text = TextButton::themed(100.0, 200.0, 200.0, 60.0, "Click Me");
+    btn_text.apply_theme(&current_theme());  // Restyle later, e.g. after switching to dark mode
+
 Then in the loop you would use:
 if btn_text.click() {
 
 }
 
+To press the button from code or the keyboard (used by the focus manager):
+    btn_text.activate();   // The next click() will return true
+
+To run code when something happens (needs pub mod events; in ui.rs, see events.rs):
+    btn_text.on_click(|| println!("Clicked"));
+    btn_text.on_hover_enter(|| println!("Mouse is over the button"));
+    btn_text.on_hover_leave(|| println!("Mouse left the button"));
+    btn_text.with_event_queue(&events, "play");  // Or send the events to a queue
+Callbacks and events run from inside click(), so keep calling it each frame.
+
 Note: For buttons with transparent backgrounds (set normal_color with alpha=0), 
 only the text area is clickable, not the entire button area.
 */
 
 use macroquad::prelude::*;
+use std::cell::Cell;
+
+use crate::ui::events::{EventHandlers, EventQueue};
+use crate::ui::theme::{Theme, current_theme};
 
 // Enum for text alignment within a button
 // Enum for vertical text alignment within a button
@@ -126,14 +146,21 @@ pub struct TextButton {
 
     // Vertical text alignment
     pub vertical_align: VerticalAlign,
+
+    // Set by activate() so the next click() returns true
+    activated: Cell<bool>,
+
+    // Callbacks and event queue
+    events: EventHandlers,
 }
 
 impl TextButton {
     pub fn new(x: f32, y: f32, width: f32, height: f32, text: impl Into<String>, normal_color: Color, hover_color: Color, font_size: u16) -> Self {
         let enabled = true;
+        let theme = current_theme();
         let off_color = lerp_color(normal_color, GRAY, 0.5);
         let text_string = text.into();
-        let text_color = WHITE; // Default text color
+        let text_color = theme.text_on_accent; // Default text color from the theme
         
         // Pre-calculate and cache values
         let cached_text_width = measure_text(&text_string, None, font_size, 1.0).width;
@@ -143,7 +170,7 @@ impl TextButton {
         );
         let cached_rect = Rect::new(x, y, width, height);
         
-        Self {
+        let mut button = Self {
             x,
             y,
             width,
@@ -157,19 +184,49 @@ impl TextButton {
             hover_text_color: text_color, // Default hover text color to regular text color
             font_size,
             font: None, // Default to None (use system font)
-            corner_radius: 0.0, // Default to no rounded corners
+            corner_radius: theme.corner_radius, // Theme rounding (0 is square)
             border: false, // Default to no border
-            border_color: BLACK, // Default border color
-            border_thickness: 1.0, // Default border thickness
+            border_color: theme.border, // Default border color
+            border_thickness: theme.border_thickness, // Default border thickness
             cached_text_width,
             cached_text_position,
             cached_rect,
             visible: true,
             text_align: TextAlign::Center, // Default to center alignment
             vertical_align: VerticalAlign::Center, // Default to center vertical alignment
+            activated: Cell::new(false),
+            events: EventHandlers::new(),
+        };
+
+        // Use the theme font if there is one
+        if let Some(font) = theme.font {
+            button.with_font(font);
         }
-       
-       
+        button
+    }
+
+    // Create a button that takes its colors and font size from the current theme
+    #[allow(unused)]
+    pub fn themed(x: f32, y: f32, width: f32, height: f32, text: impl Into<String>) -> Self {
+        let theme = current_theme();
+        Self::new(x, y, width, height, text, theme.button, theme.button_hover, theme.font_size)
+    }
+
+    // Restyle the button with a theme (keeps the font size)
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.normal_color = theme.button;
+        self.hover_color = theme.button_hover;
+        self.off_color = lerp_color(theme.button, GRAY, 0.5);
+        self.text_color = theme.text_on_accent;
+        self.hover_text_color = theme.text_on_accent;
+        self.border_color = theme.border;
+        self.border_thickness = theme.border_thickness;
+        self.corner_radius = theme.corner_radius;
+        if let Some(font) = &theme.font {
+            self.with_font(font.clone());
+        }
+        self
     }
      /// Set the vertical text alignment for the button
     #[allow(unused)]
@@ -314,8 +371,61 @@ impl TextButton {
         self
     }
 
+    // Press the button without the mouse, the next click() will return true
+    #[allow(unused)]
+    pub fn activate(&mut self) -> &mut Self {
+        if self.visible && self.enabled {
+            self.activated.set(true);
+        }
+        self
+    }
+
+    // Run a function when the button is clicked
+    #[allow(unused)]
+    pub fn on_click<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_click(callback);
+        self
+    }
+
+    // Run a function when the mouse moves onto the button
+    #[allow(unused)]
+    pub fn on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_enter(callback);
+        self
+    }
+
+    // Run a function when the mouse moves off the button
+    #[allow(unused)]
+    pub fn on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_leave(callback);
+        self
+    }
+
+    // Run a function when the button gains or loses keyboard focus
+    #[allow(unused)]
+    pub fn on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_focus(callback);
+        self
+    }
+
+    // Send this button's events to a queue under the given id
+    #[allow(unused)]
+    pub fn with_event_queue(&mut self, queue: &EventQueue, id: &str) -> &mut Self {
+        self.events.set_queue(queue, id);
+        self
+    }
+
+    // Called by the focus manager when the button gains or loses focus
+    #[allow(unused)]
+    pub fn set_focused(&mut self, focused: bool) -> &mut Self {
+        self.events.focus(focused);
+        self
+    }
+
     pub fn click(&self) -> bool {
         if !self.visible {
+            self.activated.set(false);
+            self.events.hover(false);
             return false; // If not visible, don't process clicks
         }
         // Get mouse position
@@ -340,6 +450,7 @@ impl TextButton {
             // Otherwise use the full button area
             self.cached_rect.contains(mouse_pos)
         };
+        self.events.hover(is_hovered);
 
         // Draw the text button with a bit of depth so it reads as a clickable control.
         let is_pressed = is_hovered && self.enabled && is_mouse_button_down(MouseButton::Left);
@@ -513,8 +624,13 @@ impl TextButton {
             }
         }
 
-        // After drawing, check if the button was clicked
-        is_hovered && self.enabled && is_mouse_button_released(MouseButton::Left)
+        // After drawing, check if the button was clicked (or activated from the keyboard)
+        let activated = self.activated.replace(false);
+        let clicked = self.enabled && ((is_hovered && is_mouse_button_released(MouseButton::Left)) || activated);
+        if clicked {
+            self.events.click();
+        }
+        clicked
     }
 }
 
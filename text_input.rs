@@ -21,6 +21,8 @@ Or to manunal add if adding to Cargo.toml:
 
 In your src/ui.rs file add the following to the end of the file
         pub mod text_input;
+        pub mod theme;
+        pub mod events;
 
 Add the following in the screen you want to use the textinput in with the other use statements
     use crate::ui::text_input::TextInput;
@@ -69,6 +71,9 @@ APPEARANCE CUSTOMIZATION:
     txt_input.set_enabled(true);  // Enable the text input
     txt_input.set_disabled_color(Color::new(0.7, 0.7, 0.7, 0.5)); // Customize disabled appearance
 
+    // Hide or show the text input (hidden inputs are not drawn and ignore input)
+    txt_input.set_visible(false);
+
 TEXT MANIPULATION:
     // Get current text
     let current_text = txt_input.get_text();
@@ -90,6 +95,14 @@ TEXT MANIPULATION:
     // Or set both at once
     txt_input.with_key_repeat_settings(0.3, 0.03);
 
+EVENTS (needs pub mod events; in ui.rs, see events.rs):
+    txt_input.on_change(|text| println!("Text is now {}", text));   // User changed the text
+    txt_input.on_submit(|text| println!("Enter pressed: {}", text)); // Enter in a single line input
+    txt_input.on_focus(|focused| println!("Active: {}", focused));   // Became active or inactive
+    txt_input.on_hover_enter(|| println!("Mouse over input"));
+    txt_input.on_hover_leave(|| println!("Mouse left input"));
+    txt_input.with_event_queue(&events, "name");                     // Or send events to a queue
+
 Then in the main loop you would use:
     // Update and draw the textbox in one step
     txt_input.draw();
@@ -99,6 +112,9 @@ Then in the main loop you would use:
 use crate::utils::scale::mouse_position_world as mouse_position;
 use macroquad::prelude::*;
 
+use crate::ui::events::{EventHandlers, EventQueue};
+use crate::ui::theme::{Theme, current_theme};
+
 #[cfg(target_arch = "wasm32")]
 #[allow(dead_code)]
 #[link(wasm_import_module = "env")]
@@ -206,6 +222,8 @@ pub struct TextInput {
     selection_anchor: Option<usize>, // Selection anchor byte index for range selection
     is_dragging_selection: bool,     // Tracks active mouse drag selection
     drag_start_position: Option<(f32, f32)>, // Track where drag started to distinguish click from drag
+    visible: bool,                   // Whether the text input is drawn and reacts to input
+    events: EventHandlers,           // Callbacks and event queue
 }
 
 impl TextInput {
@@ -425,6 +443,7 @@ impl TextInput {
     }
 
     pub fn new(x: f32, y: f32, width: f32, height: f32, font_size: f32) -> Self {
+        let theme = current_theme();
         Self {
             preferred_col: None,
             #[cfg(target_arch = "wasm32")]
@@ -439,13 +458,13 @@ impl TextInput {
             cursor_timer: 0.0,
             cursor_visible: true,
             font_size,
-            text_color: BLACK,           // Default color for text
-            border_color: DARKGRAY,      // Default color for border
-            background_color: LIGHTGRAY, // Default color for background
-            cursor_color: BLACK,         // Default color for cursor
-            font: None,                  // Default to None (use system font)
-            prompt: None,                // Default to None (no prompt text)
-            prompt_color: GRAY,          // Default color for prompt text
+            text_color: theme.text,             // Default colors come from the theme
+            border_color: theme.outline,
+            background_color: theme.surface,
+            cursor_color: theme.text,
+            font: theme.font.clone(),           // Theme font (None uses the system font)
+            prompt: None,                       // Default to None (no prompt text)
+            prompt_color: theme.muted,
             // Initialize key repeat values
             key_repeat_delay: 0.4, // 400ms initial delay before repeat
             key_repeat_rate: 0.05, // 50ms between repeats after initial delay
@@ -459,6 +478,8 @@ impl TextInput {
             selection_anchor: None,
             is_dragging_selection: false,
             drag_start_position: None,
+            visible: true,
+            events: EventHandlers::new(),
         }
     }
 
@@ -554,6 +575,17 @@ impl TextInput {
         self
     }
 
+    // Restyle the text input with a theme
+    #[allow(unused)]
+    pub fn apply_theme(&mut self, theme: &Theme) -> &mut Self {
+        self.with_colors(theme.text, theme.outline, theme.surface, theme.text);
+        self.prompt_color = theme.muted;
+        if let Some(font) = &theme.font {
+            self.font = Some(font.clone());
+        }
+        self
+    }
+
     // Method to set custom font
     #[allow(unused)]
     pub fn with_font(&mut self, font: Font) -> &mut Self {
@@ -592,6 +624,56 @@ impl TextInput {
         self
     }
 
+    // Called by the focus manager, same as set_active but also sends the focus event
+    #[allow(unused)]
+    pub fn set_focused(&mut self, focused: bool) -> &mut Self {
+        self.set_active(focused);
+        self.events.focus(focused);
+        self
+    }
+
+    // Run a function when the user changes the text
+    #[allow(unused)]
+    pub fn on_change<F: Fn(&str) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_text_change(callback);
+        self
+    }
+
+    // Run a function when Enter is pressed in a single line input
+    #[allow(unused)]
+    pub fn on_submit<F: Fn(&str) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_submit(callback);
+        self
+    }
+
+    // Run a function when the input becomes active (true) or inactive (false)
+    #[allow(unused)]
+    pub fn on_focus<F: Fn(bool) + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_focus(callback);
+        self
+    }
+
+    // Run a function when the mouse moves onto the input
+    #[allow(unused)]
+    pub fn on_hover_enter<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_enter(callback);
+        self
+    }
+
+    // Run a function when the mouse moves off the input
+    #[allow(unused)]
+    pub fn on_hover_leave<F: Fn() + 'static>(&mut self, callback: F) -> &mut Self {
+        self.events.set_on_hover_leave(callback);
+        self
+    }
+
+    // Send this input's events to a queue under the given id
+    #[allow(unused)]
+    pub fn with_event_queue(&mut self, queue: &EventQueue, id: &str) -> &mut Self {
+        self.events.set_queue(queue, id);
+        self
+    }
+
     // Cursor index getters/setters
     #[allow(unused)]
     pub fn get_cursor_index(&self) -> usize {
@@ -739,6 +821,27 @@ impl TextInput {
             self.active = false; // Deactivate if disabled
             self.clear_selection();
             self.is_dragging_selection = false;
+            self.events.focus(false);
+            self.events.hover(false);
+        }
+        self
+    }
+
+    // Visibility getters/setters
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    #[allow(unused)]
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        if !visible {
+            self.active = false; // Hidden inputs can't keep keyboard focus
+            self.clear_selection();
+            self.is_dragging_selection = false;
+            self.events.focus(false);
+            self.events.hover(false);
         }
         self
     }
@@ -792,20 +895,50 @@ impl TextInput {
     // Primary method - both updates and draws the textbox
     #[allow(unused)]
     pub fn draw(&mut self) {
-        self.update_internal();
+        if !self.visible {
+            return;
+        }
+        self.update_with_events();
         self.draw_internal();
     }
 
     // For cases when only drawing is needed without updating
     #[allow(unused)]
     pub fn draw_only(&self) {
+        if !self.visible {
+            return;
+        }
         self.draw_internal();
     }
 
     // For cases when only updating is needed without drawing
     #[allow(unused)]
     pub fn update_only(&mut self) {
+        if !self.visible {
+            return;
+        }
+        self.update_with_events();
+    }
+
+    // Run the update and send any events caused by it
+    fn update_with_events(&mut self) {
+        let old_text = self.text.clone();
+        let was_active = self.active;
+
         self.update_internal();
+
+        let (mx, my) = mouse_position();
+        self.events.hover(mx >= self.x && mx <= self.x + self.width && my >= self.y && my <= self.y + self.height);
+
+        if self.active != was_active {
+            self.events.focus(self.active);
+        }
+        if self.text != old_text {
+            self.events.text_changed(&self.text);
+        }
+        if was_active && self.active && !self.multiline && (is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter)) {
+            self.events.submit(&self.text);
+        }
     }
 
     // Now private - internal implementation only
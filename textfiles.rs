@@ -1,375 +1,711 @@
 /*
 Made by: Mathew Dusome
-Jul 31 2026
-Turso (libSQL) database module for Rust
-. Add to mod.rs: pub mod database;
-2. Sign up: https://turso.tech
-3. Create DB: turso db create my-db
-4. Get URL: turso db show my-db
-5. Get token: turso db tokens create my-db
-
-6. Add dependencies to Cargo.toml. Add these 2 lines to [dependencies]:
-    In the termal, run:
+April 30 2025
+Updated: Oct 16 2026 - storage backends (files, localStorage, memory), JSON save data,
+                       versioned saves with migrations
+To import you need:
+Adds TextFile functionality for cross-platform file operations
+
+For web support (WebAssembly) only:
+    Make sure dusome_bundle.js is loaded by your index.html (it provides the
+    localStorage functions this module uses). No extra crates are needed.
+
+Used for Everything:
+
+    In your utils.rs file located in the root folder add the following to the end of the file
+        pub mod textfiles;
+
+    Add with the other use statements
+        use crate::utils::textfiles::TextFile;
+
+    TextFile also needs serde and serde_json (used by save_json/load_json).
+    In the terminal run:
         cargo add serde@1.0 --features derive
         cargo add serde_json@1.0
-        cargo add ureq@2.9 --features json --target 'cfg(not(target_arch = "wasm32"))'
-
-    Or manually add to Cargo.toml in the [dependencies] section:
-        
-        serde = { version = "1.0", features = ["derive"] }
-        serde_json = "1.0"
-      
-      and add this to the [target.'cfg(not(target_arch = "wasm32"))'.dependencies] section: (create it if it doesn't exist)
-        ureq = { version = "2.9", features = ["json"] }
-   
-7.  Follow the instructions db-directions.md to set up your Cloudflare Worker backend. This is required for the database module to work in both native and WASM builds.
-
-8. Add use statement:
-    use crate::utils::database::{create_database_client, DatabaseTable};
-9. Add to mod.rs:
-    pub mod database;
-
-
-================================
-CUSTOMIZE YOUR DATABASE SCHEMA:
-================================
-1. Modify the DatabaseTable struct below
-   - Add/remove fields to match your table columns
-   - Use appropriate Rust types: i32 for INTEGER, String for TEXT, bool for BOOLEAN, f64 for REAL
-   - Keep id: i32 (0 for INSERT means auto-generate, populated with actual ID for SELECT)
-   - Use serde attributes for custom naming if needed
-
-2. Create your table in Turso (via CLI or SQL):
-   
-   Using Turso CLI:
-     turso db shell my-db
-     CREATE TABLE my_table (
-       id INTEGER PRIMARY KEY AUTOINCREMENT,
-       column1 TEXT NOT NULL,
-       column2 INTEGER,
-       ...
-     );
-
- 
-3. Column type mapping:
-   - INTEGER → i32, i64
-   - TEXT → String
-   - REAL → f64
-   - BOOLEAN → bool
-   - NUMERIC → f64 or String
-
-================================
-USAGE EXAMPLES:
-================================
-
-// NOTE: The table used in these examples is called 'messages'.
-    let client = create_database_client();
-
-// Fetch all records (for display)
-    let mut records: Vec<DatabaseTable> = Vec::new();
-    let fetched_results = client.fetch_table("messages").await;
-    if let Ok(result) = fetched_results {
-        records = result;
-       // To update a ListView with these records:
-        // update_listview(&mut list_view, &records);
+
+
+Simple examples:
+
+1. Save different data to separate files:
+
+    // Save string data (player names)
+    let names = vec!["Alice", "Bob", "Charlie"];
+    let result = TextFile::save_strings("player_names.txt", names).await;
+    if let Err(e) = result {
+        println!("Error saving names: {}", e);
+    }
+
+    // Save integer data (scores)
+    let scores = vec![100, 85, 92];
+    let result = TextFile::save_numbers("high_scores.txt", scores).await;
+    if let Err(e) = result {
+        println!("Error saving scores: {}", e);
+    }
+
+
+2. Load different data from separate files:
+
+    // Load player names
+    let result = TextFile::load_strings("player_names.txt").await;
+    Without error checking:
+    let names: Vec<String> = result.unwrap_or_default();
+
+    With error checking:
+    if let Ok(names) = result {
+        for name in names {
+            println!("Player: {}", name);
         }
-    } else {
-       println!("Error fetching records from database: {} ",fetched_results.err().unwrap());
+    } else if let Err(e) = result {
+        println!("Error loading names: {}", e);
     }
 
-     if let Ok(Some(record)) = client.fetch_record_by_id::<DatabaseTable>("message", id).await {
-                println!("Successfully fetched record from database.");
-      else if let Ok(None) = client.fetch_record_by_id::<DatabaseTable>("message", id).await {
-                println!("No record found with id {}", id);
-      } else if let Err(err) = client.fetch_record_by_id::<DatabaseTable>("message", id).await {
-                println!("Error fetching record from database: {}", err);
-      }
+    // Load high scores
+    let result = TextFile::load_numbers::<i32>("high_scores.txt").await;
+    if let Ok(scores) = result {
+        for score in scores {
+            println!("Score: {}", score);
+        }
+    } else if let Err(e) = result {
+        println!("Error loading scores: {}", e);
+    }
+
+
+3. Load game configuration from an asset file:
 
-// Insert a record (from user text input)
-    let new_record = DatabaseTable { id: 0, text: "User entered text".to_string() };
-    let insert_results =  client.insert_record("messages", &new_record).await;
-    if let Ok(id) = insert_results {
-        // Inserted, id contains the new record's id
-    } else {
-        println!("Error inserting records from database: {} ",insert_results.err().unwrap());
+    let result = TextFile::load_asset("assets/config.txt").await;
+    if let Ok(content) = result {
+        for line in content.lines() {
+            println!("Config: {}", line);
+        }
+    } else if let Err(e) = result {
+        println!("Error loading config: {}", e);
     }
 
+4. Check for or delete a saved file:
 
-// Update a record by id (Can only do one column at a time with this method)
-    if let Ok(updated_count) = client.update_record_by_id("messages", 5, "text", "New text").await {
-        // updated_count is the number of records updated
-    } else {
-        // Handle error
+    if TextFile::exists("high_scores.txt").await.unwrap_or(false) {
+        TextFile::remove("high_scores.txt").await.ok();
     }
 
-// Update a record by struct (update all non-id fields)
-    let updated_record = DatabaseTable { id: 5, text: "Updated text".to_string() };
-    if let Ok(updated_count) = client.update_record_by_struct("messages", &updated_record).await {
-        // updated_count is the number of records updated
-    } else {
-        // Handle error
+5. Save and load a whole struct (works the same on desktop and web):
+
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Default)]
+    struct GameState {
+        level: u32,
+        score: i32,
+        player_name: String,
+        inventory: Vec<String>,
+    }
+
+    // Save
+    let state = GameState { level: 3, score: 1200, ..Default::default() };
+    if let Err(e) = TextFile::save_json("savegame.json", &state).await {
+        println!("Error saving game: {}", e);
     }
 
-// Delete a record by id (from user id input)
-    if let Ok(deleted_count) = client.delete_record_by_id("messages", 5).await {
-        // deleted_count is the number of records deleted
-    } else {
-        // Handle error
+    // Load (an error if there is no save yet or the file is broken)
+    match TextFile::load_json::<GameState>("savegame.json").await {
+        Ok(state) => println!("Back to level {}", state.level),
+        Err(e) => println!("Error loading game: {}", e),
     }
 
+    // Or start from GameState::default() when there is no save yet
+    let state: GameState = TextFile::load_json_or_default("savegame.json").await.unwrap_or_default();
+
+    Tip: add #[serde(default)] above the struct so saves made before you added a field
+    still load (the new field gets its default value).
+
+6. Versioned saves, for when a struct changes in a way #[serde(default)] can't handle
+   (a field is renamed, split up, or changes type):
+
+    use crate::utils::textfiles::{TextFile, Migrations};
+    use serde_json::json;
+
+    // The current version is 3. Each step upgrades the data by one version.
+    let mut migrations = Migrations::new(3);
+    migrations
+        .add(1, |mut data| {                 // Version 1 -> 2: "hp" was renamed "health"
+            data["health"] = data["hp"].take();
+            Ok(data)
+        })
+        .add(2, |mut data| {                 // Version 2 -> 3: lives was added
+            data["lives"] = json!(3);
+            Ok(data)
+        });
+
+    // Save (the file remembers it was written as version 3)
+    TextFile::save_versioned("savegame.json", &state, &migrations).await?;
+
+    // Load (older files are upgraded step by step before being turned into GameState)
+    let state: GameState = TextFile::load_versioned("savegame.json", &migrations).await?;
+
+    Files saved with save_json (no version) are treated as version 1, so you can switch an
+    existing game over to save_versioned without losing old saves.
+
+
+Storage backends:
+    Where data is saved is decided by a storage backend. By default that is:
+        FileStorage   - files on disk (desktop)
+        LocalStorage  - the browser's localStorage (web)
+    There is also:
+        MemoryStorage - keeps everything in memory, nothing touches the disk.
+                        Handy for tests, or for a "guest" mode that shouldn't save.
+
+    To switch backend (affects every TextFile call after it):
+        use crate::utils::textfiles::{TextFile, MemoryStorage};
+        use std::rc::Rc;
+
+        let memory = Rc::new(MemoryStorage::new());
+        TextFile::set_backend(memory.clone());
+        TextFile::save_strings("names.txt", vec!["Alice"]).await.unwrap();
+        assert_eq!(memory.get("names.txt"), Some("Alice".to_string()));
+
+        TextFile::use_default_backend();   // Back to files / localStorage
+
+    You can also write your own backend by implementing StorageBackend.
+
+Platform notes:
+- On desktop: Saves files with the exact filename you provide (include .txt extension)
+  Missing folders in the path are created for you. Files are written to a .tmp file first
+  and then renamed, so a crash while saving never leaves a half written file.
+- On web: Uses the browser's localStorage (through dusome_bundle.js) with the same API
+- Asset loading works on both platforms, but web requires files in the assets directory
+*/
+
+use macroquad::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+/// Somewhere TextFile can save and load text
+pub trait StorageBackend {
+    /// Read what was saved under `name`, or None if nothing was saved yet
+    fn read(&self, name: &str) -> Result<Option<String>, String>;
 
-// Displaying records in a ListView:
-//Where 'list_view' is your ListView instance and 'records' is the Vec<DatabaseTable> fetched from the database.
-//Change the items.push! line to customize how each record is displayed in the list.
-   
-   fn update_listview(list_view: &mut ListView, messages: &Vec<DatabaseTable>) {
-    list_view.clear();
-    let mut items: Vec<String> = Vec::new();
-    for (i, msg) in messages.iter().enumerate() {
-        items.push(format!("  {}: ID={}, Text={}", i + 1, msg.id, msg.text));
+    /// Save `contents` under `name`, replacing anything already there
+    fn write(&self, name: &str, contents: &str) -> Result<(), String>;
+
+    /// Delete what was saved under `name` (not an error if it doesn't exist)
+    fn remove(&self, name: &str) -> Result<(), String>;
+
+    /// Check if anything is saved under `name`
+    fn exists(&self, name: &str) -> Result<bool, String> {
+        Ok(self.read(name)?.is_some())
     }
-    list_view.add_items(&items);
 }
-*/
 
-use serde::{Deserialize, Serialize};
+/// Saves to files on disk (desktop only)
 #[cfg(not(target_arch = "wasm32"))]
-use ureq;
+pub struct FileStorage;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StorageBackend for FileStorage {
+    fn read(&self, name: &str) -> Result<Option<String>, String> {
+        match std::fs::read_to_string(name) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read file {}: {}", name, e)),
+        }
+    }
+
+    fn write(&self, name: &str, contents: &str) -> Result<(), String> {
+        // Create any missing folders in the path first
+        let parent = std::path::Path::new(name).parent();
+        if let Some(parent) = parent.filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create folder {}: {}", parent.display(), e))?;
+        }
+
+        // Write to a temp file and rename it over the real one, so a crash part way
+        // through saving leaves the old file untouched instead of half written
+        let temp = format!("{}.tmp", name);
+        let write_temp = || -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&temp)?;
+            std::io::Write::write_all(&mut file, contents.as_bytes())?;
+            file.sync_all()
+        };
+
+        if let Err(e) = write_temp().and_then(|_| std::fs::rename(&temp, name)) {
+            let _ = std::fs::remove_file(&temp);
+            return Err(format!("Failed to write to file {}: {}", name, e));
+        }
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<(), String> {
+        match std::fs::remove_file(name) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove file {}: {}", name, e)),
+        }
+    }
+
+    fn exists(&self, name: &str) -> Result<bool, String> {
+        Ok(std::path::Path::new(name).is_file())
+    }
+}
+
+// localStorage functions from dusome_bundle.js
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "env")]
+unsafe extern "C" {
+    fn js_local_storage_set(key_ptr: *const u8, key_len: usize, value_ptr: *const u8, value_len: usize);
+    fn js_local_storage_get(key_ptr: *const u8, key_len: usize, out_ptr: *mut u8, out_len: usize) -> i32;
+    fn js_local_storage_remove(key_ptr: *const u8, key_len: usize);
+}
+
+/// Saves to the browser's localStorage (web only)
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage;
+
 #[cfg(target_arch = "wasm32")]
-use macroquad::prelude::next_frame;
+impl StorageBackend for LocalStorage {
+    fn read(&self, name: &str) -> Result<Option<String>, String> {
+        let key = name.as_bytes();
+        let mut buffer = vec![0u8; 4096];
+
+        // JS returns the full length of the value (or -1 if the key doesn't exist),
+        // so if our buffer was too small we grow it and ask again
+        loop {
+            let len = unsafe {
+                js_local_storage_get(key.as_ptr(), key.len(), buffer.as_mut_ptr(), buffer.len())
+            };
+            if len < 0 {
+                return Ok(None);
+            }
+
+            let len = len as usize;
+            if len <= buffer.len() {
+                buffer.truncate(len);
+                return String::from_utf8(buffer)
+                    .map(Some)
+                    .map_err(|e| format!("Failed to load from storage key '{}': {}", name, e));
+            }
+            buffer = vec![0u8; len];
+        }
+    }
+
+    fn write(&self, name: &str, contents: &str) -> Result<(), String> {
+        let key = name.as_bytes();
+        let value = contents.as_bytes();
+        unsafe {
+            js_local_storage_set(key.as_ptr(), key.len(), value.as_ptr(), value.len());
+        }
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<(), String> {
+        let key = name.as_bytes();
+        unsafe {
+            js_local_storage_remove(key.as_ptr(), key.len());
+        }
+        Ok(())
+    }
+}
+
+/// Keeps everything in memory (works everywhere, nothing is saved when the program ends)
+#[derive(Default)]
+pub struct MemoryStorage {
+    files: RefCell<HashMap<String, String>>,
+}
+
+#[allow(dead_code)]
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look at what is stored under a name
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.files.borrow().get(name).cloned()
+    }
 
-// Helper function for serde to skip serializing id when it's 0
-fn is_zero(num: &i32) -> bool {
-    *num == 0
+    /// Names of everything stored, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.files.borrow().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Remove everything
+    pub fn clear(&self) {
+        self.files.borrow_mut().clear();
+    }
 }
 
-// URL of your Cloudflare Worker backend
-pub const WORKER_URL: &str = "https://db-worker.mathew-dusome.workers.dev";
-
-
-// ============================================================================
-// CUSTOMIZE THIS STRUCT FOR YOUR DATABASE SCHEMA
-// ============================================================================
-
-/// Your database record struct - used for both INSERT and SELECT operations
-/// When inserting: set id to 0 (it will be auto-generated by the database)
-/// When fetching: id will be populated with the actual ID
-/// 
-/// Modify this struct to match your table columns:
-/// #[derive(Debug, Deserialize, Serialize, Clone)]
-/// pub struct DatabaseTable {
-///     #[serde(default, skip_serializing_if = "is_zero")]
-///     pub id: i32,
-///     pub email: String,
-///     pub age: i32,
-///     pub active: bool,
-///     pub score: f64,
-/// }
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct DatabaseTable {
-    #[serde(default, skip_serializing_if = "is_zero")]
-    pub id: i32,
-    pub text: String,
-    // Example: Add more fields like this:
-    // pub email: String,
-    // pub age: i32,
-    // pub active: bool,
-    // pub score: f64,
+impl StorageBackend for MemoryStorage {
+    fn read(&self, name: &str) -> Result<Option<String>, String> {
+        Ok(self.get(name))
+    }
+
+    fn write(&self, name: &str, contents: &str) -> Result<(), String> {
+        self.files.borrow_mut().insert(name.to_string(), contents.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<(), String> {
+        self.files.borrow_mut().remove(name);
+        Ok(())
+    }
 }
 
-// ============================================================================
-// CONVENIENCE FUNCTIONS
-// ============================================================================
+// Files on desktop, localStorage on web
+fn default_backend() -> Rc<dyn StorageBackend> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Rc::new(LocalStorage)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Rc::new(FileStorage)
+    }
+}
 
-#[allow(unused)]
-pub fn create_database_client() -> DatabaseClient {
-    DatabaseClient::new(WORKER_URL.to_string())
+// The backend every TextFile call uses
+thread_local! {
+    static BACKEND: RefCell<Rc<dyn StorageBackend>> = RefCell::new(default_backend());
 }
 
+/// TextFile is a utility module for reading and writing text files
+/// that works across all platforms, including web.
+pub struct TextFile;
+
+impl TextFile {
+    /// Send all TextFile calls to a different storage backend
+    #[allow(dead_code)]
+    pub fn set_backend(backend: Rc<dyn StorageBackend>) {
+        BACKEND.with(|current| *current.borrow_mut() = backend);
+    }
+
+    /// Go back to files (desktop) or localStorage (web)
+    #[allow(dead_code)]
+    pub fn use_default_backend() {
+        Self::set_backend(default_backend());
+    }
+
+    /// The storage backend TextFile is currently using
+    pub fn backend() -> Rc<dyn StorageBackend> {
+        BACKEND.with(|current| current.borrow().clone())
+    }
+
+    /// Saves a vector of strings to a file or local storage (for web)
+    pub async fn save(name: &str, data: Vec<String>) -> Result<(), String> {
+        let joined = data.join("\n");
+        Self::backend().write(name, &joined)
+    }
+
+    /// Loads a vector of strings from a file or local storage (for web)
+    /// Returns an empty vector if nothing has been saved yet
+    pub async fn load(name: &str) -> Result<Vec<String>, String> {
+        let content = Self::backend().read(name)?.unwrap_or_default();
+        Ok(content.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Checks if a file (or storage key on web) has been saved
+    #[allow(dead_code)]
+    pub async fn exists(name: &str) -> Result<bool, String> {
+        Self::backend().exists(name)
+    }
+
+    /// Deletes a file (or storage key on web), does nothing if it doesn't exist
+    #[allow(dead_code)]
+    pub async fn remove(name: &str) -> Result<(), String> {
+        Self::backend().remove(name)
+    }
+
+    /// Saves a vector of strings to a file or local storage (for web)
+    /// Convenience method that takes Vec<&str> directly
+    pub async fn save_strings<T: AsRef<str>>(name: &str, data: Vec<T>) -> Result<(), String> {
+        let string_data: Vec<String> = data.into_iter()
+            .map(|s| s.as_ref().to_string())
+            .collect();
+        Self::save(name, string_data).await
+    }
+
+    /// Saves a vector of numbers to a file or local storage (for web)
+    /// Handles any type that can be converted to a string
+    #[allow(dead_code)]
+    pub async fn save_numbers<T: ToString>(name: &str, data: Vec<T>) -> Result<(), String> {
+        let string_data: Vec<String> = data.into_iter()
+            .map(|n| n.to_string())
+            .collect();
+        Self::save(name, string_data).await
+    }
+
+    /// Loads a vector of strings from a file or local storage (for web)
+    /// Alias for load() for consistent naming with save_strings()
+    pub async fn load_strings(name: &str) -> Result<Vec<String>, String> {
+        Self::load(name).await
+    }
+
+    /// Loads a vector of numbers from a file or local storage (for web)
+    /// Handles any type that can be parsed from a string
+    #[allow(dead_code)]
+    pub async fn load_numbers<T>(name: &str) -> Result<Vec<T>, String>
+    where
+        T: std::str::FromStr,
+    {
+        let strings = Self::load(name).await?;
+
+        let mut numbers = Vec::with_capacity(strings.len());
+        for (line, s) in strings.iter().enumerate() {
+            match s.trim().parse::<T>() {
+                Ok(n) => numbers.push(n),
+                Err(_) => return Err(format!("Failed to parse '{}' as number on line {} of {}", s, line + 1, name))
+            }
+        }
+
+        Ok(numbers)
+    }
+
+    /// Saves any value that implements Serialize as JSON
+    #[allow(dead_code)]
+    pub async fn save_json<T: Serialize>(name: &str, value: &T) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to convert {} to JSON: {}", name, e))?;
+        Self::backend().write(name, &json)
+    }
+
+    /// Loads a value saved with save_json()
+    /// Returns an error if nothing has been saved yet or the data doesn't match T
+    #[allow(dead_code)]
+    pub async fn load_json<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+        match Self::backend().read(name)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to read JSON from {}: {}", name, e)),
+            None => Err(format!("Nothing saved as {}", name)),
+        }
+    }
+
+    /// Loads a value saved with save_json(), or T::default() if nothing has been saved yet
+    /// Still returns an error if the saved data is broken, so it isn't silently replaced
+    #[allow(dead_code)]
+    pub async fn load_json_or_default<T: DeserializeOwned + Default>(name: &str) -> Result<T, String> {
+        match Self::backend().read(name)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to read JSON from {}: {}", name, e)),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Saves a value as JSON along with the current version from `migrations`
+    #[allow(dead_code)]
+    pub async fn save_versioned<T: Serialize>(name: &str, value: &T, migrations: &Migrations) -> Result<(), String> {
+        let data = serde_json::to_value(value)
+            .map_err(|e| format!("Failed to convert {} to JSON: {}", name, e))?;
+        let file = serde_json::json!({ VERSION_KEY: migrations.current(), DATA_KEY: data });
+        Self::save_json(name, &file).await
+    }
+
+    /// Loads a value saved with save_versioned() (or save_json()),
+    /// upgrading it with `migrations` if it was saved by an older version
+    #[allow(dead_code)]
+    pub async fn load_versioned<T: DeserializeOwned>(name: &str, migrations: &Migrations) -> Result<T, String> {
+        let file: Value = Self::load_json(name).await?;
+        let (version, data) = split_versioned(file);
+        let data = migrations
+            .migrate(data, version)
+            .map_err(|e| format!("Failed to upgrade {}: {}", name, e))?;
+        serde_json::from_value(data).map_err(|e| format!("Failed to read JSON from {}: {}", name, e))
+    }
+
+    /// Loads an asset file (read-only data)
+    #[allow(dead_code)]
+    pub async fn load_asset(path: &str) -> Result<String, String> {
+        match load_string(path).await {
+            Ok(content) => Ok(content),
+            Err(e) => Err(format!("Failed to load asset '{}': {:?}", path, e))
+        }
+    }
 
-/// Create a table with custom name and schema
-/// The table name and columns are fully customizable
-/// Update this function if you want to change the table structure
-/// 
-/// Example for different schemas:
-/// ```
-/// // For users table:
-/// CREATE TABLE users (
-///   id INTEGER PRIMARY KEY AUTOINCREMENT,
-///   email TEXT NOT NULL UNIQUE,
-///   age INTEGER,
-///   active BOOLEAN DEFAULT 1,
-///   score REAL
-/// )
-/// 
-/// // For products table:
-/// CREATE TABLE products (
-///   id INTEGER PRIMARY KEY AUTOINCREMENT,
-///   name TEXT NOT NULL,
-///   price REAL,
-///   in_stock BOOLEAN
-/// )
-/// ```
-
-pub struct DatabaseClient {
-    worker_url: String,
 }
 
-impl DatabaseClient {
-        #[allow(unused)]    
-        pub async fn insert_record<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, Box<dyn std::error::Error>> {
-            let payload = serde_json::json!({
-                "action": "insert",
-                "table": table,
-                "record": record
-            });
-            let resp = self.send_request(&payload).await?;
-            Ok(resp["id"].as_i64().unwrap_or(0))
+// Names used for the header that save_versioned() writes
+const VERSION_KEY: &str = "save_version";
+const DATA_KEY: &str = "data";
+
+// Split a loaded file into its version and data (files without a header are version 1)
+fn split_versioned(file: Value) -> (u32, Value) {
+    if let Value::Object(mut map) = file {
+        let has_header = map.len() == 2 && map.contains_key(DATA_KEY);
+        if let Some(version) = map.get(VERSION_KEY).and_then(Value::as_u64).filter(|_| has_header) {
+            return (version as u32, map.remove(DATA_KEY).unwrap_or(Value::Null));
         }
+        return (1, Value::Object(map));
+    }
+    (1, file)
+}
+
+/// One upgrade step, takes the data of one version and returns the next version
+pub type MigrationStep = Box<dyn Fn(Value) -> Result<Value, String>>;
 
-        #[allow(unused)]
-        pub async fn update_record_by_struct<T: Serialize>(&self, table: &str, record: &T) -> Result<i64, Box<dyn std::error::Error>> {
-            let payload = serde_json::json!({
-                "action": "update",
-                "table": table,
-                "record": record
-            });
-            let resp = self.send_request(&payload).await?;
-            Ok(resp["updated"].as_i64().unwrap_or(0))
+/// Steps that upgrade saved data from older versions to the current one
+pub struct Migrations {
+    current: u32,
+    steps: BTreeMap<u32, MigrationStep>,
+}
+
+#[allow(dead_code)]
+impl Migrations {
+    /// `current` is the version your game saves now (versions start at 1)
+    pub fn new(current: u32) -> Self {
+        Self {
+            current: current.max(1),
+            steps: BTreeMap::new(),
         }
+    }
 
-        #[allow(unused)]
-        pub async fn update_record_by_id(&self, table: &str, id: i64, column: &str, value: &serde_json::Value) -> Result<i64, Box<dyn std::error::Error>> {
-            let payload = serde_json::json!({
-                "action": "update_by_column",
-                "table": table,
-                "id": id,
-                "column": column,
-                "value": value
-            });
-            let resp = self.send_request(&payload).await?;
-            Ok(resp["updated"].as_i64().unwrap_or(0))
+    /// Add the step that upgrades data saved as version `from` to version `from + 1`
+    pub fn add<F>(&mut self, from: u32, step: F) -> &mut Self
+    where
+        F: Fn(Value) -> Result<Value, String> + 'static,
+    {
+        self.steps.insert(from, Box::new(step));
+        self
+    }
+
+    /// The version new saves are written as
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Upgrade data saved as `version` to the current version, one step at a time
+    pub fn migrate(&self, mut data: Value, version: u32) -> Result<Value, String> {
+        if version > self.current {
+            return Err(format!(
+                "Saved as version {} but this game only knows up to version {}",
+                version, self.current
+            ));
         }
 
-        #[allow(unused)]
-        pub async fn delete_record_by_id(&self, table: &str, id: i64) -> Result<i64, Box<dyn std::error::Error>> {
-            let payload = serde_json::json!({
-                "action": "delete",
-                "table": table,
-                "id": id
-            });
-            let resp = self.send_request(&payload).await?;
-            Ok(resp["deleted"].as_i64().unwrap_or(0))
+        for from in version.max(1)..self.current {
+            let step = self
+                .steps
+                .get(&from)
+                .ok_or_else(|| format!("No migration from version {} to {}", from, from + 1))?;
+            data = step(data).map_err(|e| format!("Migration from version {} failed: {}", from, e))?;
         }
-        
-        #[allow(unused)]
-        async fn send_request(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-            let body = payload.to_string();
-            
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let url = &self.worker_url;
-                let response = ureq::post(url)
-                    .set("Content-Type", "application/json")
-                    .send_string(&body);
-                let text = match response {
-                    Ok(resp) => resp.into_string()?,
-                    Err(ureq::Error::Status(code, resp)) => {
-                        let err_body = resp.into_string().unwrap_or_else(|_| "Could not read error body".to_string());
-                        return Err(format!("HTTP {} error: {}", code, err_body).into());
-                    }
-                    Err(e) => return Err(e.into()),
-                };
-                let json: serde_json::Value = serde_json::from_str(&text)?;
-                return Ok(json);
-            }
-            
-            #[cfg(target_arch = "wasm32")]
-            {
-                #[link(wasm_import_module = "env")]
-                extern "C" {
-                    fn mq_db_query(ptr: *const u8, len: usize, url_ptr: *const u8, url_len: usize);
-                    fn mq_db_query_result_len() -> usize;
-                    fn mq_db_query_fill_result(ptr: *mut u8);
-                    fn mq_db_query_clear_result();
-                }
-                
-                let url_bytes = self.worker_url.as_bytes();
-                let json_bytes = body.as_bytes();
-                // Call JS: mq_db_query(ptr, len, url_ptr, url_len)
-                unsafe {
-                    mq_db_query(
-                        json_bytes.as_ptr(),
-                        json_bytes.len(),
-                        url_bytes.as_ptr(),
-                        url_bytes.len(),
-                    );
-                }
-                let mut tries = 0;
-                let max_tries = 100;
-                let mut result_len = 0;
-                while tries < max_tries {
-                    result_len = unsafe { mq_db_query_result_len() };
-                    if result_len > 0 {
-                        break;
-                    }
-                    tries += 1;
-                    next_frame().await;
-                }
-                if result_len == 0 {
-                    return Err("No result from JS db_query (timeout or JS error)".into());
-                }
-                let mut buf = vec![0u8; result_len];
-                unsafe {
-                    mq_db_query_fill_result(buf.as_mut_ptr());
-                    mq_db_query_clear_result();
-                }
-                let text = String::from_utf8(buf).map_err(|e| format!("UTF-8 error: {}", e))?;
-                let json: serde_json::Value = serde_json::from_str(&text)?;
-                return Ok(json);
-            }
-            
-            #[cfg(not(any(target_arch = "wasm32", not(target_arch = "wasm32"))))]
-            {
-                unreachable!("This should never be reached");
+        Ok(data)
+    }
+}
+
+// Helpers for the tests of every file that saves through TextFile
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use super::*;
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    // TextFile calls finish straight away, so polling until ready is enough
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
             }
         }
-    pub fn new(worker_url: String) -> Self {
-        Self { worker_url }
     }
 
+    // Files are kept in memory for the rest of the test
+    pub(crate) fn memory() -> Rc<MemoryStorage> {
+        let storage = Rc::new(MemoryStorage::new());
+        TextFile::set_backend(storage.clone());
+        storage
+    }
+}
 
-   
-    #[allow(unused)]
-    pub async fn fetch_table<T: for<'de> Deserialize<'de>>(&self, table: &str) -> Result<Vec<T>, Box<dyn std::error::Error>> {
-        let payload = serde_json::json!({
-            "action": "fetch",
-            "table": table
+#[cfg(test)]
+mod tests {
+    use super::test_helpers::{block_on, memory};
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Player {
+        name: String,
+        lives: u32,
+        coins: u32,
+    }
+
+    // Version 1 had a score, version 2 added lives, version 3 renamed score to coins
+    fn migrations() -> Migrations {
+        let mut migrations = Migrations::new(3);
+        migrations.add(1, |mut data| {
+            data["lives"] = json!(3);
+            Ok(data)
         });
-        let resp = self.send_request(&payload).await?;
-        let records = resp["records"].as_array().cloned().unwrap_or_default();
-        let mut result = Vec::new();
-        for record in records {
-            result.push(serde_json::from_value(record)?);
-        }
-        Ok(result)
-    }
-    #[allow(unused)]
-    pub async fn fetch_record_by_id<T: for<'de> Deserialize<'de>>(&self, table: &str, id: i64) -> Result<Option<T>, Box<dyn std::error::Error>> {
-        let payload = serde_json::json!({
-            "action": "fetch_by_id",
-            "table": table,
-            "id": id
+        migrations.add(2, |mut data| {
+            let score = data.as_object_mut().and_then(|map| map.remove("score")).ok_or("missing score")?;
+            data["coins"] = score;
+            Ok(data)
         });
-        let resp = self.send_request(&payload).await?;
-        let record = resp.get("record").cloned();
-        match record {
-            Some(val) if !val.is_null() => Ok(Some(serde_json::from_value(val)?)),
-            _ => Ok(None)
-        }
+        migrations
     }
 
+    fn expected(lives: u32) -> Player {
+        Player { name: "Sam".to_string(), lives, coins: 40 }
+    }
 
+    #[test]
+    fn loads_version_1_fixture() {
+        // Saved with save_json() before versions existed, so there is no header
+        let storage = memory();
+        storage.write("player.json", r#"{ "name": "Sam", "score": 40 }"#).unwrap();
+        let player: Player = block_on(TextFile::load_versioned("player.json", &migrations())).unwrap();
+        assert_eq!(player, expected(3));
+    }
+
+    #[test]
+    fn loads_version_2_fixture() {
+        let storage = memory();
+        storage
+            .write("player.json", r#"{ "save_version": 2, "data": { "name": "Sam", "score": 40, "lives": 1 } }"#)
+            .unwrap();
+        let player: Player = block_on(TextFile::load_versioned("player.json", &migrations())).unwrap();
+        assert_eq!(player, expected(1));
+    }
+
+    #[test]
+    fn saves_as_current_version() {
+        let storage = memory();
+        let data = json!({ "name": "Sam", "lives": 2, "coins": 40 });
+        block_on(TextFile::save_versioned("player.json", &data, &migrations())).unwrap();
+        let saved: Value = serde_json::from_str(&storage.get("player.json").unwrap()).unwrap();
+        assert_eq!(saved["save_version"], 3);
+        let player: Player = block_on(TextFile::load_versioned("player.json", &migrations())).unwrap();
+        assert_eq!(player, expected(2));
+    }
 
-    // All direct SQL and WASM FFI helpers removed; only HTTP/Worker logic remains
+    #[test]
+    fn missing_step_is_an_error() {
+        let storage = memory();
+        storage.write("player.json", r#"{ "name": "Sam", "score": 40 }"#).unwrap();
+        let mut migrations = Migrations::new(3);
+        migrations.add(2, Ok);
+        let result = block_on(TextFile::load_versioned::<Player>("player.json", &migrations));
+        assert!(result.unwrap_err().contains("No migration from version 1 to 2"));
+    }
+
+    #[test]
+    fn newer_version_is_an_error() {
+        let storage = memory();
+        storage.write("player.json", r#"{ "save_version": 4, "data": {} }"#).unwrap();
+        let result = block_on(TextFile::load_versioned::<Player>("player.json", &migrations()));
+        assert!(result.unwrap_err().contains("only knows up to version 3"));
+    }
+
+    #[test]
+    fn data_that_looks_like_a_header_is_not_split() {
+        let (version, data) = split_versioned(json!({ "save_version": 2, "data": 1, "extra": true }));
+        assert_eq!(version, 1);
+        assert_eq!(data["extra"], true);
+    }
 }
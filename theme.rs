@@ -0,0 +1,177 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Adds a shared Theme (colors, fonts, sizes, borders and spacing) that
+every UI object reads its default look from
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod theme;
+Label, ListView, Slider, ProgressBar, TextInput, MessageBox and TextButton read their
+defaults from this module, so it must be in ui.rs whenever one of them is.
+
+Then add the following with the use commands:
+    use crate::ui::theme::{Theme, set_theme, current_theme};
+
+If you never set a theme, objects look exactly like they always have (Theme::light()).
+
+Then above the loop, BEFORE creating your objects, pick a theme:
+    set_theme(Theme::dark());
+
+    // Or change parts of a theme
+    let mut theme = Theme::light();
+    theme.highlight = ORANGE;
+    theme.corner_radius = 8.0;
+    theme.font = Some(load_ttf_font("assets/font.ttf").await.unwrap());
+    set_theme(theme);
+
+    // Or load one from a JSON file (needs pub mod theme_file; in ui.rs, see theme_file.rs)
+    set_theme(Theme::load("assets/theme.json").await.unwrap_or_default());
+
+Objects created after set_theme() use the theme. Anything you set with with_colors(),
+with_font(), with_border() etc. after creating an object still wins.
+
+To swap themes while the game is running (for a light/dark toggle):
+    set_theme(Theme::dark());
+    current_theme().apply(&mut [&mut btn_play, &mut lbl_title, &mut volume_slider]);
+apply() is part of widget.rs (it works on any Widget), so it needs pub mod widget; in ui.rs.
+
+TextButton can also take its colors from the theme:
+    let btn = TextButton::themed(100.0, 200.0, 200.0, 60.0, "Play");
+
+This file only needs macroquad.
+*/
+
+use macroquad::prelude::*;
+use std::cell::RefCell;
+
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+
+    // Palette
+    pub text: Color,           // Normal text (labels, lists, text inputs, dialog messages)
+    pub text_on_accent: Color, // Text drawn on top of colored areas (buttons, bars, titles)
+    pub muted: Color,          // Prompt text and other less important text
+    pub background: Color,     // Dialog backgrounds
+    pub surface: Color,        // Text input and dialog button backgrounds
+    pub track: Color,          // Slider tracks and empty progress bars
+    pub border: Color,         // Borders of labels, lists and buttons
+    pub outline: Color,        // Text input borders, progress bar borders, slider handles
+    pub accent: Color,         // Dialog title bars
+    pub highlight: Color,      // Selected list items and hovered dialog buttons
+    pub hover: Color,          // Hovered slider handles
+    pub fill: Color,           // Filled part of progress bars
+    pub button: Color,         // TextButton background (used by TextButton::themed)
+    pub button_hover: Color,   // TextButton background while hovered
+
+    // Fonts
+    pub font: Option<Font>,
+    pub font_size: u16,
+    pub title_font_size: u16,
+
+    // Borders and rounding
+    pub corner_radius: f32,
+    pub border_thickness: f32,
+
+    // Spacing
+    pub padding: f32,
+    pub spacing: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[allow(unused)]
+impl Theme {
+    // The original look of every object
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            text: BLACK,
+            text_on_accent: WHITE,
+            muted: GRAY,
+            background: Color::new(0.9, 0.9, 0.9, 1.0),
+            surface: LIGHTGRAY,
+            track: GRAY,
+            border: BLACK,
+            outline: DARKGRAY,
+            accent: DARKBLUE,
+            highlight: SKYBLUE,
+            hover: BLUE,
+            fill: GREEN,
+            button: BLUE,
+            button_hover: GREEN,
+            font: None,
+            font_size: 16,
+            title_font_size: 18,
+            corner_radius: 0.0,
+            border_thickness: 1.0,
+            padding: 15.0,
+            spacing: 10.0,
+        }
+    }
+
+    // Light text on dark backgrounds
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            text: Color::new(0.92, 0.92, 0.92, 1.0),
+            text_on_accent: WHITE,
+            muted: Color::new(0.55, 0.55, 0.58, 1.0),
+            background: Color::new(0.16, 0.16, 0.18, 1.0),
+            surface: Color::new(0.24, 0.24, 0.27, 1.0),
+            track: Color::new(0.35, 0.35, 0.38, 1.0),
+            border: Color::new(0.45, 0.45, 0.48, 1.0),
+            outline: Color::new(0.6, 0.6, 0.63, 1.0),
+            accent: Color::new(0.2, 0.3, 0.55, 1.0),
+            highlight: Color::new(0.25, 0.45, 0.75, 1.0),
+            hover: Color::new(0.4, 0.65, 1.0, 1.0),
+            fill: Color::new(0.25, 0.7, 0.4, 1.0),
+            button: Color::new(0.22, 0.36, 0.62, 1.0),
+            button_hover: Color::new(0.3, 0.48, 0.8, 1.0),
+            ..Self::light()
+        }
+    }
+}
+
+// The theme used by objects when they are created
+thread_local! {
+    static CURRENT_THEME: RefCell<Theme> = RefCell::new(Theme::light());
+}
+
+// Set the theme for objects created from now on
+#[allow(unused)]
+pub fn set_theme(theme: Theme) {
+    CURRENT_THEME.with(|current| *current.borrow_mut() = theme);
+}
+
+// Get a copy of the current theme
+#[allow(unused)]
+pub fn current_theme() -> Theme {
+    CURRENT_THEME.with(|current| current.borrow().clone())
+}
+
+// Turn "#RRGGBB" or "#RRGGBBAA" into a Color
+pub fn color_from_hex(hex: &str) -> Result<Color, String> {
+    let digits = hex.trim().trim_start_matches('#');
+    if (digits.len() != 6 && digits.len() != 8) || !digits.is_ascii() {
+        return Err(format!("Invalid color: {}", hex));
+    }
+
+    let channel = |start: usize| {
+        u8::from_str_radix(&digits[start..start + 2], 16).map_err(|_| format!("Invalid color: {}", hex))
+    };
+    let alpha = if digits.len() == 8 { channel(6)? } else { 255 };
+
+    Ok(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+}
+
+// Turn a Color into "#RRGGBBAA"
+pub fn color_to_hex(color: Color) -> String {
+    let [r, g, b, a]: [u8; 4] = color.into();
+    format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+}
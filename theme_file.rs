@@ -0,0 +1,176 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Loads and saves a Theme (see theme.rs) as a JSON file
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod theme;
+    pub mod theme_file;
+
+This file needs serde and serde_json. In the terminal run:
+    cargo add serde@1.0 --features derive
+    cargo add serde_json@1.0
+
+Nothing else needs to be added to your use commands, it gives Theme these methods:
+    let theme = Theme::load("assets/theme.json").await.unwrap_or_default(); // Loads its font too
+    let theme = Theme::from_json(&text).unwrap();                           // Fonts are not loaded
+    let text = theme.to_json();                                             // Every entry filled in
+
+Example theme file (every entry is optional, missing entries come from "base"):
+    {
+        "name": "Ocean",
+        "base": "dark",
+        "text": "#E0F0FF",
+        "accent": "#004080",
+        "highlight": "#00A0C0FF",
+        "font": "assets/ocean.ttf",
+        "font_size": 18,
+        "corner_radius": 6.0
+    }
+Colors are written as "#RRGGBB" or "#RRGGBBAA".
+*/
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::theme::{color_from_hex, color_to_hex, Theme};
+
+#[allow(unused)]
+impl Theme {
+    // Read a theme from JSON text (fonts are not loaded, use Theme::load for that)
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: ThemeFile = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to read theme: {}", e))?;
+        file.into_theme()
+    }
+
+    // Load a theme file, including its font if it names one
+    pub async fn load(path: &str) -> Result<Self, String> {
+        let json = load_string(path)
+            .await
+            .map_err(|e| format!("Failed to load theme {}: {}", path, e))?;
+
+        let file: ThemeFile = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to read theme {}: {}", path, e))?;
+        let font_path = file.font.clone();
+        let mut theme = file.into_theme()?;
+
+        if let Some(font_path) = font_path {
+            let font = load_ttf_font(&font_path)
+                .await
+                .map_err(|e| format!("Failed to load font {}: {}", font_path, e))?;
+            theme.font = Some(font);
+        }
+
+        Ok(theme)
+    }
+
+    // Write the theme as JSON, ready to be saved as a theme file
+    // (the font can't be saved, add a "font" entry with its path by hand)
+    pub fn to_json(&self) -> String {
+        let file = ThemeFile {
+            name: Some(self.name.clone()),
+            base: None,
+            text: Some(color_to_hex(self.text)),
+            text_on_accent: Some(color_to_hex(self.text_on_accent)),
+            muted: Some(color_to_hex(self.muted)),
+            background: Some(color_to_hex(self.background)),
+            surface: Some(color_to_hex(self.surface)),
+            track: Some(color_to_hex(self.track)),
+            border: Some(color_to_hex(self.border)),
+            outline: Some(color_to_hex(self.outline)),
+            accent: Some(color_to_hex(self.accent)),
+            highlight: Some(color_to_hex(self.highlight)),
+            hover: Some(color_to_hex(self.hover)),
+            fill: Some(color_to_hex(self.fill)),
+            button: Some(color_to_hex(self.button)),
+            button_hover: Some(color_to_hex(self.button_hover)),
+            font: None,
+            font_size: Some(self.font_size),
+            title_font_size: Some(self.title_font_size),
+            corner_radius: Some(self.corner_radius),
+            border_thickness: Some(self.border_thickness),
+            padding: Some(self.padding),
+            spacing: Some(self.spacing),
+        };
+        serde_json::to_string_pretty(&file).unwrap_or_default()
+    }
+}
+
+// What a theme file looks like, every entry is optional
+#[derive(Serialize, Deserialize, Default)]
+struct ThemeFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base: Option<String>,
+    text: Option<String>,
+    text_on_accent: Option<String>,
+    muted: Option<String>,
+    background: Option<String>,
+    surface: Option<String>,
+    track: Option<String>,
+    border: Option<String>,
+    outline: Option<String>,
+    accent: Option<String>,
+    highlight: Option<String>,
+    hover: Option<String>,
+    fill: Option<String>,
+    button: Option<String>,
+    button_hover: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<String>,
+    font_size: Option<u16>,
+    title_font_size: Option<u16>,
+    corner_radius: Option<f32>,
+    border_thickness: Option<f32>,
+    padding: Option<f32>,
+    spacing: Option<f32>,
+}
+
+impl ThemeFile {
+    // Start from the base theme and replace whatever the file sets
+    fn into_theme(self) -> Result<Theme, String> {
+        let mut theme = match self.base.as_deref() {
+            None | Some("light") => Theme::light(),
+            Some("dark") => Theme::dark(),
+            Some(other) => return Err(format!("Unknown base theme: {}", other)),
+        };
+
+        if let Some(name) = self.name {
+            theme.name = name;
+        }
+
+        let colors = [
+            (self.text, &mut theme.text),
+            (self.text_on_accent, &mut theme.text_on_accent),
+            (self.muted, &mut theme.muted),
+            (self.background, &mut theme.background),
+            (self.surface, &mut theme.surface),
+            (self.track, &mut theme.track),
+            (self.border, &mut theme.border),
+            (self.outline, &mut theme.outline),
+            (self.accent, &mut theme.accent),
+            (self.highlight, &mut theme.highlight),
+            (self.hover, &mut theme.hover),
+            (self.fill, &mut theme.fill),
+            (self.button, &mut theme.button),
+            (self.button_hover, &mut theme.button_hover),
+        ];
+        for (hex, color) in colors {
+            if let Some(hex) = hex {
+                *color = color_from_hex(&hex)?;
+            }
+        }
+
+        theme.font_size = self.font_size.unwrap_or(theme.font_size);
+        theme.title_font_size = self.title_font_size.unwrap_or(theme.title_font_size);
+        theme.corner_radius = self.corner_radius.unwrap_or(theme.corner_radius);
+        theme.border_thickness = self.border_thickness.unwrap_or(theme.border_thickness);
+        theme.padding = self.padding.unwrap_or(theme.padding);
+        theme.spacing = self.spacing.unwrap_or(theme.spacing);
+
+        Ok(theme)
+    }
+}
@@ -0,0 +1,612 @@
+/*
+Made by: Mathew Dusome
+Oct 16, 2026
+Program Details: Adds a shared Widget trait so every UI object can be driven the same way
+
+To import you need:
+In your ui.rs file add the following to the end of the file:
+    pub mod widget;
+
+This module uses the other ui modules, so they must also be in ui.rs:
+    pub mod text_button;
+    pub mod image_button;
+    pub mod label;
+    pub mod listview;
+    pub mod slider;
+    pub mod progressbar;
+    pub mod text_input;
+    pub mod messagebox;
+    pub mod still_image;
+    pub mod animated_image;
+    pub mod events;
+    pub mod theme;
+If you are not using one of them, comment out its "use" line and its "impl Widget for" block below.
+
+Then add the following with the use commands:
+    use crate::ui::widget::{Widget, update_all, draw_all};
+
+Every object (TextButton, ImageButton, Label, ListView, Slider, ProgressBar,
+TextInput, MessageBox, StillImage, AnimatedImage) implements Widget, which gives
+them all the same set of methods:
+    bounds()              // Rect the object covers on screen
+    position()            // The x, y the object was created with
+    set_position(x, y)    // Move the object (same meaning as its own x, y)
+    move_to(x, y)         // Move so the top-left of bounds() lands on x, y
+    is_visible() / set_visible(bool)
+    is_enabled() / set_enabled(bool)
+    update()              // Handle input (does nothing for objects that do it inside draw)
+    draw()                // Draw the object
+    handle_event(&event)  // Send an InputEvent to the object, returns true if it was used
+    is_focusable()        // Can the object take keyboard focus (see focus.rs)
+    set_focused(bool)     // Tell the object it gained or lost keyboard focus
+    apply_theme(&theme)   // Restyle the object with a Theme (see theme.rs)
+
+It also adds theme.apply(&mut [...]) to restyle a list of objects at once.
+
+Then above the loop you can keep all the objects for a screen in one list:
+    let mut widgets: Vec<Box<dyn Widget>> = Vec::new();
+    widgets.push(Box::new(TextButton::new(100.0, 200.0, 200.0, 60.0, "Play", BLUE, GREEN, 30)));
+    widgets.push(Box::new(Label::new("Volume", 100.0, 320.0, 24)));
+    widgets.push(Box::new(Slider::new(100.0, 340.0, 200.0, 30.0, 0.0, 100.0, 50.0)));
+
+Then in the loop you would use:
+    update_all(&mut widgets);
+    draw_all(&mut widgets);
+
+You can still talk to a single object through the trait:
+    widgets[0].set_enabled(false);
+    widgets[1].set_visible(false);
+    if widgets[2].bounds().contains(Vec2::new(mx, my)) {
+        // Mouse is over the slider
+    }
+
+Note: Each object keeps its own methods too, so code like btn_text.click() still works.
+*/
+
+use macroquad::prelude::*;
+
+use crate::ui::animated_image::AnimatedImage;
+use crate::ui::image_button::ImageButton;
+use crate::ui::label::Label;
+use crate::ui::listview::ListView;
+use crate::ui::messagebox::MessageBox;
+use crate::ui::progressbar::ProgressBar;
+use crate::ui::slider::Slider;
+use crate::ui::still_image::StillImage;
+use crate::ui::text_button::TextButton;
+use crate::ui::text_input::TextInput;
+use crate::ui::theme::Theme;
+
+// Input that can be sent directly to a widget with handle_event()
+#[derive(Debug, Clone, PartialEq)]
+#[allow(unused)]
+pub enum InputEvent {
+    KeyPressed(KeyCode), // A key was pressed while the widget should react to it
+    CharTyped(char),     // A character was typed
+    Activate,            // The widget should act as if it was clicked
+}
+
+// Define the Widget trait
+pub trait Widget {
+    fn bounds(&self) -> Rect;
+    fn position(&self) -> Vec2;
+    fn set_position(&mut self, x: f32, y: f32);
+    fn is_visible(&self) -> bool;
+    fn set_visible(&mut self, visible: bool);
+
+    // Objects without an enabled state are always enabled
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn set_enabled(&mut self, _enabled: bool) {}
+
+    // Objects that handle their input inside draw() don't need update()
+    fn update(&mut self) {}
+
+    fn draw(&mut self);
+
+    // Returns true if the widget used the event
+    fn handle_event(&mut self, _event: &InputEvent) -> bool {
+        false
+    }
+
+    // Objects that can't be used from the keyboard are skipped by Tab
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn set_focused(&mut self, _focused: bool) {}
+
+    // Objects without colors of their own (like images) ignore themes
+    fn apply_theme(&mut self, _theme: &Theme) {}
+
+    // Move the widget so the top-left corner of its bounds is at x, y
+    // (some objects, like Label, draw above and left of their own x, y)
+    fn move_to(&mut self, x: f32, y: f32) {
+        let bounds = self.bounds();
+        let position = self.position();
+        self.set_position(x + (position.x - bounds.x), y + (position.y - bounds.y));
+    }
+
+    // Check if a point (such as the mouse) is inside the widget
+    fn contains(&self, point: Vec2) -> bool {
+        self.is_visible() && self.bounds().contains(point)
+    }
+}
+
+// Update every widget in a list
+#[allow(unused)]
+pub fn update_all(widgets: &mut [Box<dyn Widget>]) {
+    for widget in widgets.iter_mut() {
+        if widget.is_visible() {
+            widget.update();
+        }
+    }
+}
+
+// Draw every widget in a list (in order, so later widgets are drawn on top)
+#[allow(unused)]
+pub fn draw_all(widgets: &mut [Box<dyn Widget>]) {
+    for widget in widgets.iter_mut() {
+        if widget.is_visible() {
+            widget.draw();
+        }
+    }
+}
+
+// Theme::apply lives here so theme.rs doesn't need every ui module
+#[allow(unused)]
+impl Theme {
+    // Apply this theme to objects that already exist
+    pub fn apply(&self, widgets: &mut [&mut dyn Widget]) {
+        for widget in widgets.iter_mut() {
+            widget.apply_theme(self);
+        }
+    }
+}
+
+// Implement for TextButton
+impl Widget for TextButton {
+    fn bounds(&self) -> Rect {
+        Rect::new(self.get_x(), self.get_y(), self.width, self.height)
+    }
+
+    fn position(&self) -> Vec2 {
+        self.get_position()
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.update_position(x, y, None, None);
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn draw(&mut self) {
+        // click() draws the button and reports the click
+        self.click();
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        TextButton::set_focused(self, focused);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent) -> bool {
+        if *event == InputEvent::Activate {
+            self.activate();
+            return true;
+        }
+        false
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        TextButton::apply_theme(self, theme);
+    }
+}
+
+// Implement for ImageButton
+impl Widget for ImageButton {
+    fn bounds(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+
+    fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn draw(&mut self) {
+        // click() draws the button and reports the click
+        self.click();
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        ImageButton::set_focused(self, focused);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent) -> bool {
+        if *event == InputEvent::Activate {
+            self.activate();
+            return true;
+        }
+        false
+    }
+}
+
+// Implement for Label
+impl Widget for Label {
+    fn bounds(&self) -> Rect {
+        // Matches the background rectangle drawn by Label::draw
+        Rect::new(
+            self.get_x() - 5.0,
+            self.get_y() - self.get_font_size() as f32,
+            self.get_width().unwrap_or(0.0),
+            self.get_height().unwrap_or(0.0),
+        )
+    }
+
+    fn position(&self) -> Vec2 {
+        self.get_position()
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        Label::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        Label::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        Label::set_visible(self, visible);
+    }
+
+    fn draw(&mut self) {
+        Label::draw(self);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        Label::apply_theme(self, theme);
+    }
+}
+
+// Implement for ListView
+impl Widget for ListView {
+    fn bounds(&self) -> Rect {
+        self.get_bounds()
+    }
+
+    fn position(&self) -> Vec2 {
+        Vec2::new(self.get_x(), self.get_y())
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        ListView::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        ListView::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        ListView::set_visible(self, visible);
+    }
+
+    fn is_enabled(&self) -> bool {
+        ListView::is_enabled(self)
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        ListView::set_enabled(self, enabled);
+    }
+
+    fn draw(&mut self) {
+        // ListView handles scrolling and selection inside draw()
+        ListView::draw(self);
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        ListView::set_focused(self, focused);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent) -> bool {
+        match event {
+            InputEvent::KeyPressed(KeyCode::Down) => self.select_next(),
+            InputEvent::KeyPressed(KeyCode::Up) => self.select_previous(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        ListView::apply_theme(self, theme);
+    }
+}
+
+// Implement for Slider
+impl Widget for Slider {
+    fn bounds(&self) -> Rect {
+        self.get_bounds()
+    }
+
+    fn position(&self) -> Vec2 {
+        Vec2::new(self.get_x(), self.get_y())
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        Slider::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        Slider::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        Slider::set_visible(self, visible);
+    }
+
+    fn is_enabled(&self) -> bool {
+        Slider::is_enabled(self)
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        Slider::set_enabled(self, enabled);
+    }
+
+    fn update(&mut self) {
+        Slider::update(self);
+    }
+
+    fn draw(&mut self) {
+        Slider::draw(self);
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        Slider::set_focused(self, focused);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent) -> bool {
+        match event {
+            InputEvent::KeyPressed(KeyCode::Right) | InputEvent::KeyPressed(KeyCode::Up) => self.step(1.0),
+            InputEvent::KeyPressed(KeyCode::Left) | InputEvent::KeyPressed(KeyCode::Down) => self.step(-1.0),
+            _ => return false,
+        }
+        true
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        Slider::apply_theme(self, theme);
+    }
+}
+
+// Implement for ProgressBar
+impl Widget for ProgressBar {
+    fn bounds(&self) -> Rect {
+        self.get_bounds()
+    }
+
+    fn position(&self) -> Vec2 {
+        Vec2::new(self.get_x(), self.get_y())
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        ProgressBar::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        ProgressBar::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        ProgressBar::set_visible(self, visible);
+    }
+
+    fn draw(&mut self) {
+        // ProgressBar runs its animation inside draw()
+        ProgressBar::draw(self);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        ProgressBar::apply_theme(self, theme);
+    }
+}
+
+// Implement for TextInput
+impl Widget for TextInput {
+    fn bounds(&self) -> Rect {
+        Rect::new(self.get_x(), self.get_y(), self.get_width(), self.get_height())
+    }
+
+    fn position(&self) -> Vec2 {
+        let (x, y) = self.get_position();
+        Vec2::new(x, y)
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        TextInput::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        TextInput::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        TextInput::set_visible(self, visible);
+    }
+
+    fn is_enabled(&self) -> bool {
+        TextInput::is_enabled(self)
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        TextInput::set_enabled(self, enabled);
+    }
+
+    fn update(&mut self) {
+        self.update_only();
+    }
+
+    fn draw(&mut self) {
+        self.draw_only();
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    // TextInput reads the keyboard itself while it is active
+    fn set_focused(&mut self, focused: bool) {
+        TextInput::set_focused(self, focused);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        TextInput::apply_theme(self, theme);
+    }
+}
+
+// Implement for MessageBox
+impl Widget for MessageBox {
+    fn bounds(&self) -> Rect {
+        let position = self.get_position();
+        let size = self.get_size();
+        Rect::new(position.x, position.y, size.x, size.y)
+    }
+
+    fn position(&self) -> Vec2 {
+        self.get_position()
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        MessageBox::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        MessageBox::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        if visible {
+            self.show();
+        } else {
+            self.hide();
+        }
+    }
+
+    fn draw(&mut self) {
+        // Use MessageBox::draw directly when you need the button result
+        MessageBox::draw(self);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        MessageBox::apply_theme(self, theme);
+    }
+}
+
+// Implement for StillImage
+impl Widget for StillImage {
+    fn bounds(&self) -> Rect {
+        let position = self.pos();
+        let size = self.size();
+        Rect::new(position.x, position.y, size.x, size.y)
+    }
+
+    fn position(&self) -> Vec2 {
+        self.pos()
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        StillImage::set_position(self, Vec2::new(x, y));
+    }
+
+    fn is_visible(&self) -> bool {
+        StillImage::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        StillImage::set_visible(self, visible);
+    }
+
+    fn draw(&mut self) {
+        StillImage::draw(self);
+    }
+}
+
+// Implement for AnimatedImage
+impl Widget for AnimatedImage {
+    fn bounds(&self) -> Rect {
+        let position = self.pos();
+        let size = self.size();
+        Rect::new(position.x, position.y, size.x, size.y)
+    }
+
+    fn position(&self) -> Vec2 {
+        self.pos()
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        AnimatedImage::set_position(self, x, y);
+    }
+
+    fn is_visible(&self) -> bool {
+        AnimatedImage::is_visible(self)
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        AnimatedImage::set_visible(self, visible);
+    }
+
+    fn draw(&mut self) {
+        // AnimatedImage advances its frames inside draw()
+        AnimatedImage::draw(self);
+    }
+}